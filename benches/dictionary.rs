@@ -0,0 +1,24 @@
+//! Dictionary benchmarks, run with
+//! `SCRABBLE_WORDS=words.txt cargo bench --features criterion`.
+//!
+//! Set `SCRABBLE_TRE` to a saved `.tre` file to also benchmark loading.
+//! Anagrams are benchmarked using each word in the list as a rack.
+use criterion::{criterion_group, criterion_main, Criterion};
+use scrabble::bench;
+use std::{env, path::PathBuf};
+
+fn dictionary(c: &mut Criterion) {
+  let words = env::var_os("SCRABBLE_WORDS")
+    .map(PathBuf::from)
+    .map(|path| bench::read_words(&path).expect("word list should be readable"))
+    .unwrap_or_else(|| {
+      ["cat", "cats", "dog", "dogs", "bird"]
+        .map(String::from)
+        .to_vec()
+    });
+  let tre = env::var_os("SCRABBLE_TRE").map(PathBuf::from);
+  bench::register(c, &words, tre.as_deref());
+}
+
+criterion_group!(benches, dictionary);
+criterion_main!(benches);
@@ -0,0 +1,30 @@
+/*!
+Conversions between the letters `a-z` and their indices `0-25`.
+
+The checked variants return `None` outside of the alphabet, whilst the
+`_unchecked` variants assume valid input and will produce nonsense (or
+panic on overflow) when given anything else.
+*/
+
+/// The number of letters in the alphabet.
+pub const SIZE: usize = 26;
+
+/// The index of a lowercase letter, if it is in `a-z`.
+pub fn into_index(c: char) -> Option<usize> {
+  c.is_ascii_lowercase().then(|| into_index_unchecked(c))
+}
+
+/// The index of a letter, assuming it is in `a-z`.
+pub fn into_index_unchecked(c: char) -> usize {
+  (c as usize) - ('a' as usize)
+}
+
+/// The lowercase letter for an index, if it is in `0-25`.
+pub fn from_index(i: usize) -> Option<char> {
+  (i < SIZE).then(|| from_index_unchecked(i))
+}
+
+/// The lowercase letter for an index, assuming it is in `0-25`.
+pub fn from_index_unchecked(i: usize) -> char {
+  (i + ('a' as usize)) as u8 as char
+}
@@ -0,0 +1,68 @@
+/*!
+The tiles that racks are drawn from.
+*/
+
+use crate::alphabet::{into_index, SIZE};
+
+/// How many of each letter, and how many blanks, are in a bag of tiles.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bag {
+  counts: [usize; SIZE],
+  blanks: usize,
+}
+
+impl Bag {
+  pub fn new(counts: [usize; SIZE], blanks: usize) -> Self {
+    Bag { counts, blanks }
+  }
+
+  /// The standard 100 tile English distribution.
+  pub fn english() -> Self {
+    let counts = [
+      9, 2, 2, 4, 12, 2, 3, 2, 9, 1, 1, 4, 2, 6, 8, 2, 1, 6, 4, 6, 4, 2, 2, 1, 2, 1,
+    ];
+    Bag::new(counts, 2)
+  }
+
+  /// The number of tiles for a letter, or zero outside of `a-z`.
+  pub fn count(&self, c: char) -> usize {
+    into_index(c).map_or(0, |i| self.counts[i])
+  }
+
+  pub(crate) fn counts(&self) -> &[usize; SIZE] {
+    &self.counts
+  }
+
+  pub fn blanks(&self) -> usize {
+    self.blanks
+  }
+
+  /// The total number of tiles.
+  pub fn len(&self) -> usize {
+    self.counts.iter().sum::<usize>() + self.blanks
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+}
+
+/// The face value of each letter's tiles in the English game.
+pub const ENGLISH_SCORES: [u32; SIZE] = [
+  1, 3, 3, 2, 1, 4, 2, 4, 1, 8, 5, 1, 3, 1, 1, 3, 10, 1, 1, 1, 1, 4, 4, 8, 4, 10,
+];
+
+/// The total face value of a word's tiles, ignoring anything outside `a-z`.
+pub fn score(word: &str) -> u32 {
+  word
+    .chars()
+    .filter_map(into_index)
+    .map(|i| ENGLISH_SCORES[i])
+    .sum()
+}
+
+impl Default for Bag {
+  fn default() -> Self {
+    Bag::english()
+  }
+}
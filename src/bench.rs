@@ -0,0 +1,159 @@
+/*!
+Timing helpers for measuring dictionary operations on a supplied word list.
+
+The plain helpers only depend on `std::time`, so they can be called from
+examples, tests or downstream tools to compare node layouts. With the
+`criterion` feature enabled, [`register`] wires the same operations into a
+criterion benchmark group (see `benches/dictionary.rs`).
+*/
+
+use crate::trie_ptr::Trie;
+use std::{
+  fmt::Display,
+  fs::File,
+  hint::black_box,
+  io::{self, BufRead, BufReader},
+  path::Path,
+  time::{Duration, Instant},
+};
+
+/// The total time taken to run an operation some number of times.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timing {
+  pub iterations: usize,
+  pub total: Duration,
+}
+
+impl Timing {
+  /// The average time taken by a single iteration.
+  pub fn per_iter(&self) -> Duration {
+    self.total.div_f64(self.iterations.max(1) as f64)
+  }
+
+  /// The number of iterations that would complete in one second.
+  pub fn per_second(&self) -> f64 {
+    self.iterations as f64 / self.total.as_secs_f64()
+  }
+}
+
+impl Display for Timing {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(
+      f,
+      "{} iterations in {:?} ({:?}/iter, {:.0}/s)",
+      self.iterations,
+      self.total,
+      self.per_iter(),
+      self.per_second()
+    )
+  }
+}
+
+/// Runs `op` the given number of times, timing the whole run.
+pub fn time<T, F: FnMut() -> T>(iterations: usize, mut op: F) -> Timing {
+  let start = Instant::now();
+  for _ in 0..iterations {
+    black_box(op());
+  }
+  Timing {
+    iterations,
+    total: start.elapsed(),
+  }
+}
+
+/// Reads a word list with one word per line, skipping blank lines.
+pub fn read_words(path: &Path) -> io::Result<Vec<String>> {
+  BufReader::new(File::open(path)?)
+    .lines()
+    .filter(|line| !matches!(line, Ok(l) if l.is_empty()))
+    .collect()
+}
+
+/// Times building a trie from the whole word list.
+pub fn build(words: &[String], iterations: usize) -> Timing {
+  time(iterations, || words.iter().cloned().collect::<Trie>())
+}
+
+/// Times loading a `.tre` or `.txt` file into a trie.
+pub fn load(path: &Path, iterations: usize) -> io::Result<Timing> {
+  // fail early rather than timing a run of errors
+  Trie::file(path)?;
+  Ok(time(iterations, || Trie::file(path)))
+}
+
+/// Times looking up every word in the list, one iteration per word.
+pub fn has(trie: &Trie, words: &[String]) -> Timing {
+  let mut iter = words.iter();
+  time(words.len(), || iter.next().map(|word| trie.has(word)))
+}
+
+/// Times finding the anagrams of every rack, one iteration per rack.
+pub fn anagrams(trie: &Trie, racks: &[String]) -> Timing {
+  let mut iter = racks.iter();
+  time(racks.len(), || iter.next().map(|rack| trie.anagrams(rack)))
+}
+
+/// Registers the dictionary benchmarks with criterion.
+///
+/// `tre` is optional as not every caller will have a saved trie to hand.
+#[cfg(feature = "criterion")]
+pub fn register(c: &mut criterion::Criterion, words: &[String], tre: Option<&Path>) {
+  let mut group = c.benchmark_group("dictionary");
+  group.bench_function("build", |b| {
+    b.iter(|| words.iter().cloned().collect::<Trie>())
+  });
+  if let Some(path) = tre {
+    group.bench_function("load", |b| b.iter(|| Trie::file(path)));
+  }
+
+  let trie: Trie = words.iter().cloned().collect();
+  group.bench_function("has", |b| {
+    let mut words = words.iter().cycle();
+    b.iter(|| words.next().map(|word| trie.has(word)))
+  });
+  // each word's own letters make a rack with at least one anagram
+  group.bench_function("anagrams", |b| {
+    let mut racks = words.iter().cycle();
+    b.iter(|| racks.next().map(|rack| trie.anagrams(rack)))
+  });
+  group.finish();
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use std::fs;
+
+  fn words() -> Vec<String> {
+    ["act", "cat", "dog", "god"].map(String::from).to_vec()
+  }
+
+  #[test]
+  fn times_iterations() {
+    // the operation should be run once per iteration
+    let mut runs = 0;
+    let timing = time(5, || runs += 1);
+    assert_eq!((runs, timing.iterations), (5, 5));
+  }
+
+  #[test]
+  fn build_and_has() {
+    // lookups should be timed once per word
+    assert_eq!(build(&words(), 3).iterations, 3);
+    let trie: Trie = words().into_iter().collect();
+    assert_eq!(has(&trie, &words()).iterations, 4);
+    assert_eq!(anagrams(&trie, &words()[..2]).iterations, 2);
+  }
+
+  #[test]
+  fn load_file() {
+    // saved tries should be timed, and missing files should fail early
+    let path = crate::temp_path("scrabble_bench_load.tre");
+    let trie: Trie = words().into_iter().collect();
+    trie.save(&path).unwrap();
+    let timing = load(&path, 2);
+    fs::remove_file(&path).unwrap();
+    assert_eq!(timing.unwrap().iterations, 2);
+    assert!(load(&path, 2).is_err());
+  }
+}
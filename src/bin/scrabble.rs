@@ -0,0 +1,72 @@
+//! The `scrabble` command line, run with
+//! `cargo run --features serve -- serve --dict words.tre --port 8080`.
+use scrabble::{lexicon::SharedLexicon, serve::serve};
+use std::{env, net::SocketAddr, path::PathBuf, process::ExitCode};
+
+const USAGE: &str = "usage: scrabble serve --dict <path> [--port <port>]";
+
+#[derive(Debug, PartialEq, Eq)]
+struct ServeArgs {
+  dict: PathBuf,
+  port: u16,
+}
+
+fn parse_serve(mut args: impl Iterator<Item = String>) -> Result<ServeArgs, String> {
+  let (mut dict, mut port) = (None, 8080);
+  while let Some(flag) = args.next() {
+    let value = args.next().ok_or_else(|| format!("`{flag}` needs a value"))?;
+    match flag.as_str() {
+      "--dict" => dict = Some(PathBuf::from(value)),
+      "--port" => port = value.parse().map_err(|_| format!("`{value}` is not a port"))?,
+      _ => return Err(format!("unknown flag `{flag}`")),
+    }
+  }
+  let dict = dict.ok_or("`--dict` is required")?;
+  Ok(ServeArgs { dict, port })
+}
+
+fn run_serve(args: ServeArgs) -> std::io::Result<()> {
+  let lexicon = SharedLexicon::file(&args.dict)?;
+  let addr = SocketAddr::from(([127, 0, 0, 1], args.port));
+  println!("serving {} on http://{addr}", args.dict.display());
+  tokio::runtime::Runtime::new()?.block_on(serve(lexicon, addr))
+}
+
+fn main() -> ExitCode {
+  let mut args = env::args().skip(1);
+  let result = match args.next().as_deref() {
+    Some("serve") => parse_serve(args)
+      .map_err(|err| format!("{err}\n{USAGE}"))
+      .and_then(|args| run_serve(args).map_err(|err| err.to_string())),
+    _ => Err(USAGE.to_owned()),
+  };
+  match result {
+    Ok(()) => ExitCode::SUCCESS,
+    Err(message) => {
+      eprintln!("{message}");
+      ExitCode::FAILURE
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn parse(args: &[&str]) -> Result<ServeArgs, String> {
+    parse_serve(args.iter().map(|arg| arg.to_string()))
+  }
+
+  #[test]
+  fn serve_args() {
+    // flags can come in any order, with the port defaulting to 8080
+    let args = parse(&["--port", "9000", "--dict", "x.tre"]).unwrap();
+    assert_eq!((args.dict, args.port), (PathBuf::from("x.tre"), 9000));
+    assert_eq!(parse(&["--dict", "x.tre"]).unwrap().port, 8080);
+    // bad input should be an error rather than a panic
+    assert!(parse(&[]).is_err());
+    assert!(parse(&["--dict"]).is_err());
+    assert!(parse(&["--dict", "x.tre", "--port", "http"]).is_err());
+    assert!(parse(&["--words", "x.tre"]).is_err());
+  }
+}
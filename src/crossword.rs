@@ -0,0 +1,240 @@
+/*!
+Fills for crossword grids, using word patterns intersected with a dictionary.
+
+A grid is written one row per line, with `#` for blocked squares, `.` for
+empty squares and `a-z` for filled ones. Each run of two or more open
+squares across or down is a slot that needs a word.
+
+Every open square keeps the set of letters it could still hold. Each slot
+is matched against the dictionary as a [`Word`] pattern built from those
+sets, and the letters that no matching word uses are removed from its
+squares. This repeats over every slot until nothing changes, so a slot's
+fills are the words that still have some match in every crossing slot.
+*/
+
+use crate::{letter::Letter, set::Set, trie_ptr::Trie, word::Word};
+use std::{error::Error, fmt::Display, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+  Block,
+  Empty,
+  Filled(char),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+  Across,
+  Down,
+}
+
+/// A run of open squares that needs a word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Slot {
+  pub row: usize,
+  pub col: usize,
+  pub direction: Direction,
+  pub len: usize,
+}
+
+impl Slot {
+  /// The row and column of each square in the slot.
+  pub fn squares(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+    (0..self.len).map(|i| match self.direction {
+      Direction::Across => (self.row, self.col + i),
+      Direction::Down => (self.row + i, self.col),
+    })
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid {
+  width: usize,
+  cells: Vec<Cell>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError {
+  /// A row had a different width to the first row.
+  Ragged { row: usize },
+  /// A square was something other than `#`, `.` or `a-z`.
+  Invalid { row: usize, found: char },
+}
+
+impl Display for GridError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      GridError::Ragged { row } => write!(f, "row {row} is a different width to the first"),
+      GridError::Invalid { row, found } => {
+        write!(f, "`{found}` in row {row} isn't `#`, `.` or a-z")
+      }
+    }
+  }
+}
+
+impl Error for GridError {}
+
+impl FromStr for Grid {
+  type Err = GridError;
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let rows: Vec<_> = s
+      .lines()
+      .map(str::trim)
+      .filter(|row| !row.is_empty())
+      .collect();
+    let width = rows.first().map_or(0, |row| row.chars().count());
+    let mut cells = vec![];
+    for (row, line) in rows.iter().enumerate() {
+      if line.chars().count() != width {
+        return Err(GridError::Ragged { row });
+      }
+      for c in line.chars() {
+        cells.push(match c {
+          '#' => Cell::Block,
+          '.' => Cell::Empty,
+          'a'..='z' => Cell::Filled(c),
+          found => return Err(GridError::Invalid { row, found }),
+        })
+      }
+    }
+    Ok(Grid { width, cells })
+  }
+}
+
+impl Grid {
+  pub fn width(&self) -> usize {
+    self.width
+  }
+
+  pub fn height(&self) -> usize {
+    self.cells.len().checked_div(self.width).unwrap_or(0)
+  }
+
+  /// The square at a row and column, with anything off the grid blocked.
+  pub fn cell(&self, row: usize, col: usize) -> Cell {
+    if col >= self.width || row >= self.height() {
+      return Cell::Block;
+    }
+    self.cells[row * self.width + col]
+  }
+
+  /// Every slot in the grid, across slots first, in reading order.
+  pub fn slots(&self) -> Vec<Slot> {
+    let mut slots = vec![];
+    for direction in [Direction::Across, Direction::Down] {
+      for row in 0..self.height() {
+        for col in 0..self.width {
+          let (prev, next) = match direction {
+            Direction::Across => ((row, col.wrapping_sub(1)), (row, col + 1)),
+            Direction::Down => ((row.wrapping_sub(1), col), (row + 1, col)),
+          };
+          let open = |(r, c): (usize, usize)| self.cell(r, c) != Cell::Block;
+          if !open((row, col)) || open(prev) || !open(next) {
+            continue;
+          }
+
+          let slot = Slot {
+            row,
+            col,
+            direction,
+            len: usize::MAX,
+          };
+          let len = slot.squares().take_while(|&square| open(square)).count();
+          slots.push(Slot { len, ..slot });
+        }
+      }
+    }
+    slots
+  }
+
+  /// The words that could fill a slot, consistent with every crossing slot.
+  pub fn fills(&self, slot: &Slot, dict: &Trie) -> Vec<String> {
+    let mut options: Vec<Letter> = self
+      .cells
+      .iter()
+      .map(|cell| match cell {
+        Cell::Filled(c) => Letter::singleton(*c),
+        _ => Letter::all(),
+      })
+      .collect();
+
+    let slots = self.slots();
+    let mut changed = true;
+    while changed {
+      changed = false;
+      for other in &slots {
+        let words = self.matches(other, &options, dict);
+        for (i, (row, col)) in other.squares().enumerate() {
+          let used: Letter = words
+            .iter()
+            .map(|word| word.as_bytes()[i] as char)
+            .collect();
+          let option = &mut options[row * self.width + col];
+          let before = *option;
+          option.intersect(&used);
+          changed |= *option != before;
+        }
+      }
+    }
+    self.matches(slot, &options, dict)
+  }
+
+  fn matches(&self, slot: &Slot, options: &[Letter], dict: &Trie) -> Vec<String> {
+    let pattern: Word = slot
+      .squares()
+      .map(|(row, col)| options[row * self.width + col])
+      .collect();
+    (dict & Trie::word(pattern)).collect()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::trie_of;
+
+  fn dict() -> Trie {
+    trie_of(&["cat", "car", "cot", "act", "arc", "tar", "rat", "oat", "at", "to"])
+  }
+
+  #[test]
+  fn slots() {
+    // slots should be runs of at least two open squares
+    let grid: Grid = "c..\n.#.\n..#".parse().unwrap();
+    let slots = grid.slots();
+    let lens: Vec<_> = slots
+      .iter()
+      .map(|slot| (slot.direction, slot.row, slot.col, slot.len))
+      .collect();
+    assert_eq!(
+      lens,
+      [
+        (Direction::Across, 0, 0, 3),
+        (Direction::Across, 2, 0, 2),
+        (Direction::Down, 0, 0, 3),
+        (Direction::Down, 0, 2, 2),
+      ]
+    );
+  }
+
+  #[test]
+  fn fills() {
+    // fills should be limited by the words that cross them
+    let grid: Grid = "c..\n.#.\n..#".parse().unwrap();
+    let slots = grid.slots();
+    // the last letter across must start a two letter word down, ruling
+    // out `car`, which in turn rules out `at` down
+    assert_eq!(grid.fills(&slots[0], &dict()), ["cat", "cot"]);
+    assert_eq!(grid.fills(&slots[3], &dict()), ["to"]);
+  }
+
+  #[test]
+  fn invalid() {
+    // grids should be rectangular and only hold known squares
+    assert_eq!("ab\nc".parse::<Grid>(), Err(GridError::Ragged { row: 1 }));
+    assert_eq!(
+      "a?".parse::<Grid>(),
+      Err(GridError::Invalid { row: 0, found: '?' })
+    );
+  }
+}
@@ -1,11 +1,17 @@
 use std::{
   array,
-  borrow::Borrow,
-  ops::{Deref, DerefMut},
+  cell::RefCell,
+  collections::{HashMap, HashSet},
+  error::Error,
+  fmt::Display,
+  io::{self, Read},
+  mem,
+  ops::Deref,
   rc::{Rc, Weak},
 };
 
 use super::word::Word;
+use crate::{alphabet::into_index, memory::MemoryReport, trie::TrieNode};
 use weak_table::{traits::WeakElement, PtrWeakHashSet};
 
 /** A single node in a Directed Acyclic Word Graph (DAWG)
@@ -18,8 +24,8 @@ Every node in the Dawg needs to share the same end node, in order to make recurs
 `Dawg::children: [Option<Rc<Dawg>>; 26]`:<br/>
 We need to reserve a child for each letter and a child for the ending node. Nodes won't necessarily have a node for each letter, hence the `Option` type.
 
-`Dawg::parents: Vec<Weak<Dawg>>`:<br\>
-Whilst merging, we want to avoid checking every single node to see if it can be merged (i.e. points to all the same nodes as `self`). By storing parents for each node we can at least find another node with one pointer in common (by following the path `self.child.parent`).
+`Dawg::parents: RefCell<PtrWeakHashSet<WeakDawg>>`:<br\>
+Whilst merging, we want to avoid checking every single node to see if it can be merged (i.e. points to all the same nodes as `self`). By storing parents for each node we can at least find another node with one pointer in common (by following the path `self.child.parent`). Nodes are usually shared, so parents are registered through a `RefCell` rather than by mutating the node.
 */
 type RcLink = Rc<Node>;
 
@@ -59,24 +65,170 @@ type Parents = PtrWeakHashSet<WeakDawg>;
 pub struct Node {
   end_node: WeakDawg,
   children: Children,
-  parents: Parents,
+  parents: RefCell<Parents>,
 }
 
 /* Derefencing */
 impl Dawg {
   pub fn borrow(&self) -> impl Deref<Target = Node> + '_ {
-    self.0.as_ref().borrow()
+    self.0.as_ref()
   }
 }
 impl Deref for Dawg {
   type Target = Node;
   fn deref(&self) -> &Self::Target {
-    self.0.as_ref().borrow()
+    self.0.as_ref()
   }
 }
-impl DerefMut for Dawg {
-  fn deref_mut(&mut self) -> &mut Self::Target {
-    Rc::get_mut(&mut self.0).unwrap()
+
+/* Mutation
+
+Nodes are usually shared, so they're changed copy-on-write through
+[`Dawg::set_child`] and [`Dawg::set_end`], which never panic. There's no
+public `&mut Node`, as a copy made through one wouldn't be registered in
+its children's parent sets.
+*/
+impl Dawg {
+  /// A mutable reference to this node, copying it first if it's shared
+  /// so that other handles to it are left unchanged.
+  ///
+  /// A copy isn't yet in its children's parent sets, so this is kept
+  /// private to [`Dawg::set_child_at`], which registers it afterwards.
+  fn make_mut(&mut self) -> &mut Node {
+    Rc::make_mut(&mut self.0)
+  }
+
+  /// Registers this node as a parent of each of its children.
+  fn adopt_children(&self) {
+    for child in self.children.iter().flatten() {
+      child.parents.borrow_mut().insert(self.clone());
+    }
+  }
+
+  /// Replaces a child, keeping the parent sets of both children in step.
+  fn set_child_at(&mut self, i: usize, child: Option<Dawg>) {
+    let before = Rc::as_ptr(&self.0);
+    let old = mem::replace(&mut self.make_mut().children[i], child);
+    if Rc::as_ptr(&self.0) != before {
+      // this is a new copy, which none of its children know about yet, and
+      // the parents it was copied with are still the original's
+      self.make_mut().parents.get_mut().clear();
+      self.adopt_children();
+      return;
+    }
+
+    let still_child = |old: &Dawg| {
+      self
+        .children
+        .iter()
+        .flatten()
+        .any(|c| Rc::ptr_eq(&c.0, &old.0))
+    };
+    if let Some(old) = old.filter(|old| !still_child(old)) {
+      old.parents.borrow_mut().remove(self);
+    }
+    if let Some(child) = &self.children[i] {
+      child.parents.borrow_mut().insert(self.clone());
+    }
+  }
+
+  /// Sets the child for a letter, copying this node first if it's shared.
+  ///
+  /// Letters outside of `a-z` are ignored, as with [`TrieNode::set_child`].
+  pub fn set_child(&mut self, c: char, child: Option<Dawg>) {
+    if let Some(i) = into_index(c) {
+      self.set_child_at(i, child)
+    }
+  }
+
+  /// Sets whether this node ends a word, linking it to the end node.
+  pub fn set_end(&mut self, end: bool) {
+    let end = end.then(|| self.end_node.upgrade()).flatten();
+    self.set_child_at(26, end)
+  }
+}
+
+/// A broken invariant found by [`Dawg::validate`], located by the prefix
+/// leading to the node at fault.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DawgError {
+  /// The end node has been dropped, so words can't be ended.
+  EndDropped,
+  /// A node ends a word with a node other than the shared end node.
+  WrongEnd { prefix: String },
+  /// A child doesn't list the node at the prefix as one of its parents.
+  MissingParent { prefix: String },
+  /// The node at the prefix can reach itself again.
+  Cycle { prefix: String },
+}
+
+impl Display for DawgError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      DawgError::EndDropped => write!(f, "the end node has been dropped"),
+      DawgError::WrongEnd { prefix } => write!(f, "`{prefix}` isn't ended by the end node"),
+      DawgError::MissingParent { prefix } => {
+        write!(f, "a child of `{prefix}` doesn't list it as a parent")
+      }
+      DawgError::Cycle { prefix } => write!(f, "`{prefix}` is part of a cycle"),
+    }
+  }
+}
+
+impl Error for DawgError {}
+
+impl Dawg {
+  /// Checks that the end node is alive and shared by every node ending a
+  /// word, that every child lists its parents, and that there are no cycles.
+  pub fn validate(&self) -> Result<(), DawgError> {
+    let end = self.end_node.upgrade().ok_or(DawgError::EndDropped)?;
+    self.check(&end, &mut String::new(), &mut HashMap::new())
+  }
+
+  /// Checks this node and everything below it, where `finished` records
+  /// whether each node seen has been fully checked yet.
+  fn check(
+    &self,
+    end: &Dawg,
+    prefix: &mut String,
+    finished: &mut HashMap<*const Node, bool>,
+  ) -> Result<(), DawgError> {
+    match finished.get(&Rc::as_ptr(&self.0)) {
+      Some(true) => return Ok(()),
+      Some(false) => {
+        return Err(DawgError::Cycle {
+          prefix: prefix.clone(),
+        })
+      }
+      None => finished.insert(Rc::as_ptr(&self.0), false),
+    };
+
+    for (c, child) in ('a'..='z').zip(&self.children) {
+      let Some(child) = child else { continue };
+      if !child.parents.borrow().contains(self) {
+        return Err(DawgError::MissingParent {
+          prefix: prefix.clone(),
+        });
+      }
+      prefix.push(c);
+      child.check(end, prefix, finished)?;
+      prefix.pop();
+    }
+
+    if let Some(node) = &self.children[26] {
+      if !Rc::ptr_eq(&node.0, &end.0) {
+        return Err(DawgError::WrongEnd {
+          prefix: prefix.clone(),
+        });
+      }
+      if !node.parents.borrow().contains(self) {
+        return Err(DawgError::MissingParent {
+          prefix: prefix.clone(),
+        });
+      }
+    }
+    finished.insert(Rc::as_ptr(&self.0), true);
+    Ok(())
   }
 }
 
@@ -112,7 +264,8 @@ impl Dawg {
   /// then we can reach one node from another via the common child
   pub fn find_eq(&self) -> Option<Dawg> {
     let child = self.children.iter().find_map(|x| x.as_ref())?;
-    let mut siblings = child.parents.iter();
+    let parents = child.parents.borrow();
+    let mut siblings = parents.iter();
     siblings.find_map(|parent| Some(parent).filter(|node| self.ptr_eq(node)))
   }
 
@@ -120,18 +273,163 @@ impl Dawg {
   /// using pre-existing duplicate nodes within the Dawg
   /// This is what keeps DAWG representations distinct from tries
   pub fn merge(&mut self) {
-    for child in self.children.iter_mut().flatten() {
-      if let Some(node) = child.find_eq() {
-        *child = node
+    for i in 0..27 {
+      if let Some(node) = self.children[i].as_ref().and_then(Dawg::find_eq) {
+        self.set_child_at(i, Some(node))
       }
     }
   }
 }
 
+type Registry = HashMap<[Option<*const Node>; 27], Dawg>;
+
+impl Dawg {
+  /// Merges every pair of equivalent nodes, giving a minimal graph.
+  ///
+  /// Nodes are rebuilt bottom up, so each node is looked up in a registry
+  /// of the nodes already built only once its children are minimal.
+  pub fn minimize(&mut self) {
+    *self = self.minimized(&mut HashMap::new(), &mut Registry::new());
+  }
+
+  fn minimized(&self, done: &mut HashMap<*const Node, Dawg>, registry: &mut Registry) -> Dawg {
+    if let Some(node) = done.get(&Rc::as_ptr(&self.0)) {
+      return node.clone();
+    }
+    let mut children: Children = array::from_fn(|_| None);
+    for (i, child) in self.children[..26].iter().enumerate() {
+      children[i] = child.as_ref().map(|child| child.minimized(done, registry))
+    }
+    children[26] = self.children[26].clone();
+
+    let key = children
+      .each_ref()
+      .map(|c| c.as_ref().map(|node| Rc::as_ptr(&node.0)));
+    let node = registry
+      .entry(key)
+      .or_insert_with(|| {
+        let node = Self::from_args(self.end_node.clone(), children);
+        node.adopt_children();
+        node
+      })
+      .clone();
+    done.insert(Rc::as_ptr(&self.0), node.clone());
+    node
+  }
+}
+
 /*
 @note a dawg should have some debugging tools:
-> Display
+> Display, memory_report
 */
+impl Dawg {
+  /// Estimates the memory used by this dawg, see [`MemoryReport`].
+  ///
+  /// Nodes are identified by pointer, so shared nodes are only counted once.
+  pub fn memory_report(&self) -> MemoryReport {
+    let mut report = MemoryReport::default();
+    let mut seen = HashSet::from([Rc::as_ptr(&self.0)]);
+    let mut level = vec![self];
+    while !level.is_empty() {
+      report.depths.push(level.len());
+      report.heap_bytes += level.iter().map(|node| node.heap_bytes()).sum::<usize>();
+
+      let mut next = vec![];
+      for child in level.iter().flat_map(|node| node.children.iter().flatten()) {
+        if seen.insert(Rc::as_ptr(&child.0)) {
+          next.push(child)
+        }
+      }
+      level = next;
+    }
+
+    report.nodes = seen.len();
+    report.tree_nodes = self.tree_nodes(&mut HashMap::new());
+    report
+  }
+
+  /// The heap allocation for this node, including the `Rc` counts
+  /// and the table behind the parent set.
+  fn heap_bytes(&self) -> usize {
+    mem::size_of::<Node>()
+      + 2 * mem::size_of::<usize>()
+      + self.parents.borrow().capacity() * mem::size_of::<WeakDawg>()
+  }
+
+  /// The number of nodes this dawg would take up as a trie.
+  fn tree_nodes(&self, counts: &mut HashMap<*const Node, usize>) -> usize {
+    if let Some(&count) = counts.get(&Rc::as_ptr(&self.0)) {
+      return count;
+    }
+    let count = self.children.iter().flatten().fold(1usize, |count, child| {
+      count.saturating_add(child.tree_nodes(counts))
+    });
+    counts.insert(Rc::as_ptr(&self.0), count);
+    count
+  }
+}
+
+/// Counts describing the shape of a dawg, see [`Dawg::stats`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DawgStats {
+  /// The number of distinct nodes, not counting the shared end node.
+  pub nodes: usize,
+  /// The number of letter edges between those nodes.
+  pub edges: usize,
+  /// The number of words in the dawg.
+  pub words: usize,
+  /// How many trie nodes each distinct node stands in for on average.
+  pub sharing_ratio: f64,
+}
+
+impl Dawg {
+  fn letters(&self) -> impl Iterator<Item = &Dawg> + '_ {
+    self.children[..26].iter().flatten()
+  }
+
+  /// Counts the nodes, edges and words in this dawg, identifying nodes by
+  /// pointer so that shared nodes are only counted once.
+  pub fn stats(&self) -> DawgStats {
+    let mut seen = HashSet::from([Rc::as_ptr(&self.0)]);
+    let mut stack = vec![self];
+    let mut edges = 0;
+    while let Some(node) = stack.pop() {
+      for child in node.letters() {
+        edges += 1;
+        if seen.insert(Rc::as_ptr(&child.0)) {
+          stack.push(child)
+        }
+      }
+    }
+
+    let mut counts = HashMap::new();
+    let (words, tree_nodes) = self.path_counts(&mut counts);
+    DawgStats {
+      nodes: seen.len(),
+      edges,
+      words,
+      sharing_ratio: tree_nodes as f64 / seen.len() as f64,
+    }
+  }
+
+  /// The number of words below this node and the number of trie nodes it
+  /// would expand to, memoised by pointer.
+  fn path_counts(&self, counts: &mut HashMap<*const Node, (usize, usize)>) -> (usize, usize) {
+    if let Some(&count) = counts.get(&Rc::as_ptr(&self.0)) {
+      return count;
+    }
+    let start = (self.children[26].is_some() as usize, 1usize);
+    let count = self.letters().fold(start, |(words, nodes), child| {
+      let (child_words, child_nodes) = child.path_counts(counts);
+      (
+        words.saturating_add(child_words),
+        nodes.saturating_add(child_nodes),
+      )
+    });
+    counts.insert(Rc::as_ptr(&self.0), count);
+    count
+  }
+}
 
 /*
 @note a trie is a collection:
@@ -144,59 +442,45 @@ impl Dawg {
     Dawg(Rc::new(Node {
       end_node,
       children,
-      parents: Parents::new(),
+      parents: RefCell::default(),
     }))
   }
 
-  fn end() -> WeakDawg {
-    WeakDawg(Rc::downgrade(&Rc::new_cyclic(|node| Node {
-      end_node: WeakDawg(node.clone()),
-      children: array::from_fn(|_| None),
-      parents: Parents::new(),
-    })))
-  }
-
-  pub fn empty() -> Self {
-    Self::from_args(Self::end(), array::from_fn(|_| None))
-  }
-
-  fn add_end(mut self) -> Self {
-    let cloned = self.clone();
-    self.children[26] = self.end_node.upgrade();
-    self.children[26]
-      .as_mut()
-      .map(|node| node.parents.insert(cloned));
-    self
-  }
-
-  pub fn word(word: Word) -> Self {
+  /// Builds the nodes for a pattern, ending each word with `end`.
+  fn word_in(word: Word, end: &Dawg) -> Self {
     word.split().map_or_else(
-      || Self::empty().add_end(),
+      || {
+        let mut node = Self::from_args(end.downgrade(), array::from_fn(|_| None));
+        node.set_end(true);
+        node
+      },
       |(letter, next)| {
-        let mut node = Self::word(next);
+        let node = Self::word_in(next, end);
         let dawg = Self::from_args(
-          node.end_node.clone(),
-          array::from_fn(|i| letter.has_idx_unchecked(i).then(|| node.clone())),
+          end.downgrade(),
+          array::from_fn(|i| letter.has_idx(i).then(|| node.clone())),
         );
-        node.parents.insert(dawg.clone());
+        dawg.adopt_children();
         dawg
       },
     )
   }
 
-  pub fn str(word: &str) -> Self {
-    Self::word(word.parse().unwrap())
+  /// Whether this node ends a word.
+  pub fn is_end(&self) -> bool {
+    self.children[26].is_some()
   }
 
-  pub fn all(len: usize) -> Self {
-    Self::str(".".repeat(len).as_str())
+  /// The child for a letter, if there is one.
+  pub fn get_child(&self, c: char) -> Option<&Dawg> {
+    self.children[into_index(c)?].as_ref()
   }
 
   pub fn is_empty(&self) -> bool {
     self.children[26].is_none()
       && self.children[0..26]
         .iter()
-        .all(|c| c.as_ref().map_or(false, |node| node.is_empty()))
+        .all(|c| c.as_ref().is_none_or(|node| node.is_empty()))
   }
 
   pub fn is_leaf(&self) -> bool {
@@ -204,19 +488,107 @@ impl Dawg {
   }
 }
 
-impl From<()> for Dawg {
+/// A dawg along with the end node that its words finish on.
+///
+/// Nodes only hold a weak reference to the end node, so that the graph has
+/// no reference cycles. This keeps the end node alive for as long as the
+/// dawg is, which makes it the way to build dawgs.
+#[derive(Clone)]
+pub struct DawgRoot {
+  root: Dawg,
+  end: Dawg,
+}
+
+impl DawgRoot {
+  fn new_end() -> Dawg {
+    Dawg(Rc::new_cyclic(|node| Node {
+      end_node: WeakDawg(node.clone()),
+      children: array::from_fn(|_| None),
+      parents: RefCell::default(),
+    }))
+  }
+
+  pub fn empty() -> Self {
+    let end = Self::new_end();
+    let root = Dawg::from_args(end.downgrade(), array::from_fn(|_| None));
+    DawgRoot { root, end }
+  }
+
+  pub fn word(word: Word) -> Self {
+    let end = Self::new_end();
+    let root = Dawg::word_in(word, &end);
+    DawgRoot { root, end }
+  }
+
+  /// Creates a dawg from a pattern, ignoring everything after the first
+  /// invalid letter, see [`Word::parse_lenient`].
+  pub fn str(word: &str) -> Self {
+    Self::word(Word::parse_lenient(word))
+  }
+
+  pub fn all(len: usize) -> Self {
+    Self::str(".".repeat(len).as_str())
+  }
+
+  pub fn root(&self) -> &Dawg {
+    &self.root
+  }
+
+  /// The root node, for making changes with [`Dawg::set_child`] and co.
+  pub fn root_mut(&mut self) -> &mut Dawg {
+    &mut self.root
+  }
+
+  /// The node that every word in the dawg ends on.
+  pub fn end(&self) -> &Dawg {
+    &self.end
+  }
+}
+
+impl DawgRoot {
+  /// Builds the minimal dawg holding the same words as a trie.
+  pub fn from_node<N: TrieNode>(node: &N) -> Self {
+    let end = Self::new_end();
+    let mut root = Dawg::unshared(node, &end);
+    root.minimize();
+    DawgRoot { root, end }
+  }
+}
+
+impl Dawg {
+  /// Copies a trie node for node, without sharing any of them.
+  fn unshared<N: TrieNode>(node: &N, end: &Dawg) -> Dawg {
+    let mut dawg = Dawg::from_args(end.downgrade(), array::from_fn(|_| None));
+    for c in 'a'..='z' {
+      if let Some(sub) = node.get_child(c) {
+        dawg.set_child(c, Some(Self::unshared(sub, end)))
+      }
+    }
+    dawg.set_end(node.is_end());
+    dawg
+  }
+}
+
+impl Deref for DawgRoot {
+  type Target = Dawg;
+  fn deref(&self) -> &Self::Target {
+    &self.root
+  }
+}
+
+impl From<()> for DawgRoot {
   fn from(_: ()) -> Self {
     Self::empty()
   }
 }
 
-impl From<Word> for Dawg {
+impl From<Word> for DawgRoot {
   fn from(word: Word) -> Self {
     Self::word(word)
   }
 }
 
-impl From<&str> for Dawg {
+impl From<&str> for DawgRoot {
   fn from(word: &str) -> Self {
     Self::str(word)
   }
@@ -226,31 +598,37 @@ impl From<&str> for Dawg {
 @note a trie may need unused branches pruned to reduce size or clear out
 > prune, clear
 */
-impl Node {
+impl Dawg {
+  /// Removes the branches that don't lead to any words.
+  ///
+  /// Shared nodes are copied before they change and the end node is never
+  /// touched, so other handles and parent sets stay valid.
   pub fn prune(&mut self) {
-    for child in self.children.iter_mut() {
-      if let Some(node) = child.as_mut() {
-        node.prune();
-        if node.is_leaf() {
-          child.take();
-        }
+    for i in 0..26 {
+      let Some(child) = &self.children[i] else {
+        continue;
+      };
+      if child.is_empty() {
+        self.set_child_at(i, None);
+        continue;
+      }
+      let mut pruned = child.clone();
+      pruned.prune();
+      if !Rc::ptr_eq(&pruned.0, &child.0) {
+        self.set_child_at(i, Some(pruned));
       }
     }
   }
 
   pub fn clear(&mut self) {
-    for child in self.children.iter_mut() {
-      child.take();
+    for i in 0..self.children.len() {
+      if self.children[i].is_some() {
+        self.set_child_at(i, None);
+      }
     }
   }
 }
 
-/*
-@note a trie is a set:
-> in, add, remove, union, intersect, difference
-*/
-impl Node {}
-
 /*
 @note as tries could get quite large, they should support file operations:
 > load_trie, save_trie, load_words, save_words, load, save
@@ -260,3 +638,272 @@ Trie file format:
 a u32 number for each node, traversed in depth-first order
 <5 unused bits><1 bit for whether the node is an end><26 bits for each letter>
 */
+
+/*
+Dawg byte format, which keeps shared nodes shared:
+a u32 count of nodes, followed by each node in post-order, so the root is last
+<5 unused bits><1 bit for whether the node is an end><26 bits for each letter>
+followed by a u32 index of an earlier node for each letter set
+*/
+const END_BIT: u32 = 1 << 26;
+
+fn invalid_data(msg: String) -> io::Error {
+  io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+fn read_u32(bytes: &mut &[u8]) -> io::Result<u32> {
+  let mut buf = [0; 4];
+  bytes.read_exact(&mut buf)?;
+  Ok(u32::from_be_bytes(buf))
+}
+
+impl Dawg {
+  /// The dawg in its byte format, with each shared node written once.
+  pub fn to_bytes(&self) -> Vec<u8> {
+    let mut ids = HashMap::new();
+    let mut nodes = vec![];
+    self.write_node(&mut ids, &mut nodes);
+
+    let mut bytes = (ids.len() as u32).to_be_bytes().to_vec();
+    bytes.extend(nodes);
+    bytes
+  }
+
+  /// Writes the nodes below this one that haven't been written yet, and
+  /// then this node, returning its index.
+  fn write_node(&self, ids: &mut HashMap<*const Node, u32>, bytes: &mut Vec<u8>) -> u32 {
+    if let Some(&id) = ids.get(&Rc::as_ptr(&self.0)) {
+      return id;
+    }
+    let children: Vec<_> = self.children[..26]
+      .iter()
+      .enumerate()
+      .filter_map(|(i, child)| Some((i, child.as_ref()?.write_node(ids, bytes))))
+      .collect();
+
+    let mut header = if self.children[26].is_some() {
+      END_BIT
+    } else {
+      0
+    };
+    for (i, _) in &children {
+      header |= 1 << i
+    }
+    bytes.extend(header.to_be_bytes());
+    for (_, id) in children {
+      bytes.extend(id.to_be_bytes())
+    }
+
+    let id = ids.len() as u32;
+    ids.insert(Rc::as_ptr(&self.0), id);
+    id
+  }
+}
+
+impl DawgRoot {
+  /// Reads a dawg written by [`Dawg::to_bytes`], failing if any node
+  /// refers to one after it or there's anything after the root.
+  pub fn from_bytes(mut bytes: &[u8]) -> io::Result<Self> {
+    let end = Self::new_end();
+    let count = read_u32(&mut bytes)?;
+    let mut nodes: Vec<Dawg> = vec![];
+    for id in 0..count {
+      let header = read_u32(&mut bytes)?;
+      let mut children: Children = array::from_fn(|_| None);
+      for i in (0..26).filter(|i| header & (1 << i) > 0) {
+        let child = read_u32(&mut bytes)?;
+        let node = nodes.get(child as usize).ok_or_else(|| {
+          invalid_data(format!(
+            "Node {id} refers to node {child}, which isn't before it."
+          ))
+        })?;
+        children[i] = Some(node.clone());
+      }
+      if header & END_BIT > 0 {
+        children[26] = Some(end.clone())
+      }
+
+      let node = Dawg::from_args(end.downgrade(), children);
+      node.adopt_children();
+      nodes.push(node);
+    }
+
+    if !bytes.is_empty() {
+      return Err(invalid_data(format!(
+        "{} unexpected bytes after the root node.",
+        bytes.len()
+      )));
+    }
+    let root = nodes
+      .pop()
+      .ok_or_else(|| invalid_data("A dawg needs at least a root node.".into()))?;
+    Ok(DawgRoot { root, end })
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::trie_of;
+  use crate::trie_ptr::Trie;
+
+  /// Builds a dawg from a trie by hand, without sharing any nodes.
+  fn unshared(trie: &Trie, end: &Dawg) -> Dawg {
+    Dawg::unshared(trie, end)
+  }
+
+  fn minimal(trie: &Trie) -> Dawg {
+    DawgRoot::from_node(trie).root
+  }
+
+  #[test]
+  fn bytes() {
+    // a minimal dawg should round trip without losing any sharing
+    let trie = trie_of(&["bat", "bats", "cat", "cats", "cot"]);
+    let dawg = minimal(&trie);
+    let bytes = dawg.to_bytes();
+    let read = DawgRoot::from_bytes(&bytes).unwrap();
+    assert!(*read == dawg);
+    assert_eq!(read.stats(), dawg.stats());
+    assert!(read.validate().is_ok());
+
+    // indices to later nodes could make cycles, so should be rejected
+    let looped = [0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 0];
+    assert!(DawgRoot::from_bytes(&looped).is_err());
+    assert!(DawgRoot::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    assert!(DawgRoot::from_bytes(&[0, 0, 0, 0]).is_err());
+  }
+
+  #[test]
+  fn stats() {
+    // words sharing a suffix should share all of its nodes
+    let trie = trie_of(&["bat", "bats", "cat", "cats"]);
+    let stats = minimal(&trie).stats();
+    assert_eq!(stats.nodes, 5);
+    assert_eq!(stats.edges, 5);
+    assert_eq!(stats.words, 4);
+    assert_eq!(stats.sharing_ratio, 9.0 / 5.0);
+  }
+
+  #[test]
+  fn minimize() {
+    // minimising should keep the same words in fewer nodes
+    let trie = trie_of(&["bat", "cat", "cot"]);
+    let end = DawgRoot::new_end();
+    let mut dawg = unshared(&trie, &end);
+    assert_eq!(dawg.stats().nodes, 9);
+    dawg.minimize();
+    let stats = dawg.stats();
+    assert_eq!((stats.nodes, stats.words), (5, 3));
+    assert!(dawg == unshared(&trie, &end));
+  }
+
+  #[test]
+  fn make_mut() {
+    // mutating a shared node should leave the other handle untouched
+    let trie = Trie::str("cat");
+    let end = DawgRoot::new_end();
+    let original = unshared(&trie, &end);
+    let mut copy = original.clone();
+    copy.set_child('C', None);
+    assert!(copy.ptr_eq(&original));
+    copy.set_child('c', None);
+    assert!(copy.is_leaf());
+    assert!(!copy.ptr_eq(&original));
+    assert_eq!(original.stats().words, 1);
+  }
+
+  #[test]
+  fn copy_with_children() {
+    // a copy should be adopted by its children, without the original's parents
+    let trie = trie_of(&["at", "cat", "cot"]);
+    let dawg = DawgRoot::from_node(&trie);
+    let mut copy = dawg.children[2].clone().unwrap();
+    copy.set_child('u', copy.children[0].clone());
+    assert_eq!(copy.validate(), Ok(()));
+    assert_eq!(dawg.validate(), Ok(()));
+    assert!(copy.parents.borrow().is_empty());
+    assert_eq!(copy.stats().words, 3);
+  }
+
+  #[test]
+  fn prune() {
+    // dead branches should go, leaving the end node and other handles alone
+    let trie = trie_of(&["cat", "cot"]);
+    let mut dawg = DawgRoot::from_node(&trie);
+    let original = dawg.root().clone();
+    let mut dead = original.children[2].clone().unwrap();
+    let mut t = dead.children[0].as_ref().unwrap().children[19]
+      .clone()
+      .unwrap();
+    t.set_end(false);
+    dead.set_child('o', None);
+    dead.set_child_at(0, Some(t));
+    dawg.root_mut().set_child('d', Some(dead));
+    assert_eq!(dawg.validate(), Ok(()));
+
+    dawg.root_mut().prune();
+    assert_eq!(dawg.validate(), Ok(()));
+    assert!(dawg.children[3].is_none());
+    assert!(*dawg == original);
+    assert_eq!(dawg.stats().words, 2);
+  }
+
+  #[test]
+  fn clear() {
+    let trie = trie_of(&["at", "cat"]);
+    let dawg = DawgRoot::from_node(&trie);
+    let mut copy = dawg.root().clone();
+    copy.clear();
+    assert!(copy.is_leaf());
+    assert_eq!(copy.validate(), Ok(()));
+    assert_eq!(dawg.validate(), Ok(()));
+    assert_eq!(dawg.stats().words, 2);
+  }
+
+  #[test]
+  fn word() {
+    // words built from a pattern should all reach the end node
+    let dawg = DawgRoot::str("c[ao]t");
+    let stats = dawg.stats();
+    assert_eq!((stats.nodes, stats.words), (4, 2));
+    assert_eq!(dawg.validate(), Ok(()));
+    assert_eq!(DawgRoot::empty().stats().words, 0);
+  }
+
+  #[test]
+  fn validate() {
+    // parents should be kept in step through mutation and minimising
+    let trie = trie_of(&["bat", "cat", "cot"]);
+    let end = DawgRoot::new_end();
+    let mut dawg = unshared(&trie, &end);
+    assert_eq!(dawg.validate(), Ok(()));
+    dawg.minimize();
+    assert_eq!(dawg.validate(), Ok(()));
+
+    let mut copy = dawg.clone();
+    copy.set_child('d', dawg.children[1].clone());
+    assert_eq!(copy.validate(), Ok(()));
+    assert_eq!(dawg.validate(), Ok(()));
+  }
+
+  #[test]
+  fn invalid() {
+    let end = DawgRoot::new_end();
+    let mut children: Children = array::from_fn(|_| None);
+    children[0] = Some(unshared(&Trie::str("t"), &end));
+    let dawg = Dawg::from_args(end.downgrade(), children);
+    let missing = DawgError::MissingParent { prefix: "".into() };
+    assert_eq!(dawg.validate(), Err(missing));
+
+    let dropped = Dawg::from_args(WeakDawg(Weak::new()), array::from_fn(|_| None));
+    assert_eq!(dropped.validate(), Err(DawgError::EndDropped));
+  }
+
+  #[test]
+  fn pattern() {
+    // every position of a pattern should be a single node
+    let stats = minimal(&Trie::str("[a-e][a-e]")).stats();
+    assert_eq!((stats.nodes, stats.edges, stats.words), (3, 10, 25));
+  }
+}
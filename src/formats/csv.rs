@@ -0,0 +1,176 @@
+/*!
+Annotated word lists as CSV, for swapping with the spreadsheets study
+groups keep. Rows hold `word,definition,front_hooks,back_hooks,probability`
+under a header row, where hooks are written as their letters, i.e. `cht`,
+and empty cells are missing details.
+
+Words and hooks are read ignoring case, so `CAT` is the same as `cat`.
+*/
+use crate::{
+  lexicon::{
+    entry::{hooks_from_str, hooks_to_string},
+    Entry,
+  },
+  trie_map::TrieMap,
+};
+use std::{error::Error, fmt::Display, fs::File, io, path::Path};
+
+const HEADER: [&str; 5] = [
+  "word",
+  "definition",
+  "front_hooks",
+  "back_hooks",
+  "probability",
+];
+
+/// A reason a CSV word list couldn't be read or written.
+#[derive(Debug)]
+pub enum CsvError {
+  /// The file itself couldn't be read or written.
+  Csv(::csv::Error),
+  /// A row's word has characters outside of `a-z`.
+  Word { line: u64, word: String },
+  /// A row's probability isn't a number.
+  Probability { line: u64, value: String },
+}
+
+impl Display for CsvError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::Csv(err) => write!(f, "{err}"),
+      Self::Word { line, word } => write!(f, "line {line}: `{word}` isn't a word in a-z"),
+      Self::Probability { line, value } => {
+        write!(f, "line {line}: `{value}` isn't a probability")
+      }
+    }
+  }
+}
+
+impl Error for CsvError {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match self {
+      Self::Csv(err) => Some(err),
+      _ => None,
+    }
+  }
+}
+
+impl From<::csv::Error> for CsvError {
+  fn from(err: ::csv::Error) -> Self {
+    Self::Csv(err)
+  }
+}
+
+impl From<io::Error> for CsvError {
+  fn from(err: io::Error) -> Self {
+    Self::Csv(err.into())
+  }
+}
+
+fn cell(text: &str) -> Option<String> {
+  (!text.is_empty()).then(|| text.to_owned())
+}
+
+/// Reads rows into entries keyed on their words, with later rows for the
+/// same word replacing earlier ones.
+pub fn read<R: io::Read>(reader: R) -> Result<TrieMap<Entry>, CsvError> {
+  let mut reader = ::csv::ReaderBuilder::new()
+    .flexible(true)
+    .from_reader(reader);
+  let mut entries = TrieMap::new();
+  for record in reader.records() {
+    let record = record?;
+    let line = record.position().map_or(0, |pos| pos.line());
+    let field = |i| record.get(i).unwrap_or("").trim();
+
+    let word = field(0).to_ascii_lowercase();
+    if word.is_empty() || !word.chars().all(|c| c.is_ascii_lowercase()) {
+      let word = field(0).to_owned();
+      return Err(CsvError::Word { line, word });
+    }
+    let probability = match field(4) {
+      "" => None,
+      value => Some(value.parse().map_err(|_| CsvError::Probability {
+        line,
+        value: value.to_owned(),
+      })?),
+    };
+
+    let entry = Entry {
+      definition: cell(field(1)),
+      front_hooks: hooks_from_str(&field(2).to_ascii_lowercase()),
+      back_hooks: hooks_from_str(&field(3).to_ascii_lowercase()),
+      probability,
+      ..Entry::new(&word)
+    };
+    entries.insert(&word, entry);
+  }
+  Ok(entries)
+}
+
+/// Writes entries as rows in alphabetical order, after a header row.
+pub fn write<W: io::Write>(writer: W, entries: &TrieMap<Entry>) -> Result<(), CsvError> {
+  let mut writer = ::csv::Writer::from_writer(writer);
+  writer.write_record(HEADER)?;
+  for (word, entry) in entries.iter() {
+    writer.write_record([
+      word,
+      entry.definition.clone().unwrap_or_default(),
+      hooks_to_string(&entry.front_hooks),
+      hooks_to_string(&entry.back_hooks),
+      entry.probability.map(|p| p.to_string()).unwrap_or_default(),
+    ])?;
+  }
+  writer.flush()?;
+  Ok(())
+}
+
+/// Reads a CSV file, see [`read`].
+pub fn load(path: &Path) -> Result<TrieMap<Entry>, CsvError> {
+  read(File::open(path)?)
+}
+
+/// Writes a CSV file, see [`write`].
+pub fn save(path: &Path, entries: &TrieMap<Entry>) -> Result<(), CsvError> {
+  write(File::create(path)?, entries)
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  const SHEET: &str = "\
+word,definition,front_hooks,back_hooks,probability
+AT,\"in, on or near\",CHT,E,
+cat,a small feline,,s,0.25
+";
+
+  #[test]
+  fn round_trip() {
+    // rows should be read ignoring case and written back in lowercase
+    let entries = read(SHEET.as_bytes()).unwrap();
+    let at = entries.get("at").unwrap();
+    assert_eq!(at.definition.as_deref(), Some("in, on or near"));
+    assert_eq!(hooks_to_string(&at.front_hooks), "cht");
+    assert_eq!(at.probability, None);
+    assert_eq!(entries.get("cat").unwrap().probability, Some(0.25));
+
+    let mut written = vec![];
+    write(&mut written, &entries).unwrap();
+    let written = String::from_utf8(written).unwrap();
+    assert_eq!(written, SHEET.replace("AT", "at").replace("CHT,E", "cht,e"));
+    assert_eq!(read(written.as_bytes()).unwrap(), entries);
+  }
+
+  #[test]
+  fn bad_rows() {
+    // errors should point at the offending line
+    let word = read("word\nc4t\n".as_bytes()).unwrap_err();
+    assert_eq!(word.to_string(), "line 2: `c4t` isn't a word in a-z");
+    let probability = read("word,d,f,b,p\ncat,,,,lots\n".as_bytes()).unwrap_err();
+    assert_eq!(
+      probability.to_string(),
+      "line 2: `lots` isn't a probability"
+    );
+  }
+}
@@ -0,0 +1,7 @@
+/*!
+Reading and writing word data in formats shared with other tools, each
+behind a feature of the same name.
+*/
+
+#[cfg(feature = "csv")]
+pub mod csv;
@@ -0,0 +1,64 @@
+use super::Letter;
+use std::array;
+
+/// The bits of a `u32` used to store a [`Letter`], one for each of `a-z`.
+pub const MASK: u32 = (1 << 26) - 1;
+
+impl Letter {
+  /// Packs the letter into the lowest 26 bits of a `u32`,
+  /// with `a` as the least significant bit.
+  pub fn to_mask(&self) -> u32 {
+    self.indices().fold(0, |mask, i| mask | (1 << i))
+  }
+
+  /// Unpacks a letter from the lowest 26 bits of a `u32`.
+  ///
+  /// Any bits above [`MASK`] are ignored, so node headers that store extra
+  /// flags alongside the letters can be passed in directly.
+  pub fn from_mask(mask: u32) -> Self {
+    Self(array::from_fn(|i| mask & (1 << i) > 0))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::set::Set;
+
+  #[test]
+  fn empty() {
+    // the empty letter should have no bits set
+    assert_eq!(Letter::default().to_mask(), 0);
+    assert!(Letter::from_mask(0).is_empty());
+  }
+
+  #[test]
+  fn single() {
+    // single characters should map onto single bits
+    assert_eq!(Letter::singleton('a').to_mask(), 1);
+    assert_eq!(Letter::singleton('z').to_mask(), 1 << 25);
+  }
+
+  #[test]
+  fn all() {
+    // the full alphabet should fill the mask
+    assert_eq!(Letter::all().to_mask(), MASK);
+  }
+
+  #[test]
+  fn ignores_flags() {
+    // bits above the mask shouldn't produce letters
+    assert_eq!(Letter::from_mask(!MASK), Letter::default());
+  }
+
+  #[test]
+  #[cfg(feature = "rand")]
+  fn round_trip() {
+    // converting to a mask and back should give the same letter
+    let mut rng = rand::thread_rng();
+    for _ in 0..100 {
+      let letter: Letter = rand::Rng::gen(&mut rng);
+      assert_eq!(Letter::from_mask(letter.to_mask()), letter);
+    }
+  }
+}
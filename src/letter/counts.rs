@@ -0,0 +1,94 @@
+use crate::alphabet::{from_index_unchecked, into_index, SIZE};
+
+/// What to do with characters that aren't letters when counting text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TextPolicy {
+  /// Skip anything that isn't an ASCII letter.
+  #[default]
+  Skip,
+  /// Skip whitespace and ASCII punctuation, but reject anything else.
+  Reject,
+}
+
+/// How many times each letter appears, regardless of order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct LetterCounts([usize; SIZE]);
+
+impl LetterCounts {
+  /// Tallies the letters in some text, ignoring case and skipping anything
+  /// that isn't a letter, i.e. `"Dormitory!"` and `"dirty room"` have the
+  /// same counts.
+  pub fn from_text(text: &str) -> Self {
+    Self::from_text_with(text, TextPolicy::Skip).expect("nothing is rejected when skipping")
+  }
+
+  /// Tallies the letters in some text, ignoring case, returning the first
+  /// character the policy rejects if there is one.
+  pub fn from_text_with(text: &str, policy: TextPolicy) -> Result<Self, char> {
+    let mut counts = LetterCounts::default();
+    for c in text.chars() {
+      match into_index(c.to_ascii_lowercase()) {
+        Some(i) => counts.0[i] += 1,
+        None if policy == TextPolicy::Skip => {}
+        None if c.is_whitespace() || c.is_ascii_punctuation() => {}
+        None => return Err(c),
+      }
+    }
+    Ok(counts)
+  }
+
+  /// The number of times a letter appears, or zero outside of `a-z`.
+  pub fn get(&self, c: char) -> usize {
+    into_index(c).map_or(0, |i| self.0[i])
+  }
+
+  /// The total number of letters counted.
+  pub fn total(&self) -> usize {
+    self.0.iter().sum()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.total() == 0
+  }
+
+  /// Whether every letter in other appears here at least as many times.
+  pub fn covers(&self, other: &LetterCounts) -> bool {
+    self.0.iter().zip(other.0).all(|(&have, need)| have >= need)
+  }
+
+  pub(crate) fn counts(&self) -> &[usize; SIZE] {
+    &self.0
+  }
+
+  /// The letters in alphabetical order, repeated by their counts.
+  pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+    let repeated = |(i, &n)| std::iter::repeat_n(from_index_unchecked(i), n);
+    self.0.iter().enumerate().flat_map(repeated)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn from_text() {
+    // letters should be counted regardless of case or punctuation
+    let counts = LetterCounts::from_text("Dormitory!");
+    assert_eq!(counts, LetterCounts::from_text("dirty room"));
+    assert_eq!(counts.get('o'), 2);
+    assert_eq!(counts.total(), 9);
+    assert_eq!(counts.chars().collect::<String>(), "dimoorrty");
+  }
+
+  #[test]
+  fn reject() {
+    // rejecting should only allow letters, spaces and punctuation
+    let counts = LetterCounts::from_text_with("it's a cat", TextPolicy::Reject);
+    assert_eq!(counts.map(|c| c.total()), Ok(7));
+    assert_eq!(
+      LetterCounts::from_text_with("4 cats", TextPolicy::Reject),
+      Err('4')
+    );
+  }
+}
@@ -0,0 +1,90 @@
+use std::{error::Error, fmt::Display};
+
+/// Where and why a letter or word pattern failed to parse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+  /// The full text that was being parsed.
+  pub input: String,
+  /// The byte offset of the offending character.
+  pub offset: usize,
+  /// A description of what could have been parsed instead.
+  pub expected: &'static str,
+  /// The offending character, or `None` at the end of the input.
+  pub found: Option<char>,
+}
+
+impl ParseError {
+  /// Creates an error from the input left over when parsing stopped.
+  pub(crate) fn new(input: &str, remaining: &str) -> Self {
+    let offset = input.len() - remaining.len();
+    let opened = input[..offset].rfind('[');
+    let in_group = opened.is_some_and(|i| !input[i..offset].contains(']'));
+    ParseError {
+      input: input.to_owned(),
+      offset,
+      expected: match in_group {
+        true => "a letter, `-` or `]`",
+        false => "a letter, `.` or `[`",
+      },
+      found: remaining.chars().next(),
+    }
+  }
+}
+
+impl Display for ParseError {
+  /// Renders the error with a caret under the offending character, i.e.
+  ///
+  /// ```text
+  /// expected a letter, `.` or `[` but found `!` at 3
+  /// abc!!
+  ///    ^
+  /// ```
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "expected {} but found ", self.expected)?;
+    match self.found {
+      Some(c) => write!(f, "`{c}`")?,
+      None => write!(f, "the end")?,
+    }
+    let column = self.input[..self.offset].chars().count();
+    write!(f, " at {}\n{}\n{:column$}^", self.offset, self.input, "")
+  }
+}
+
+impl Error for ParseError {}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  #[cfg(feature = "parse")]
+  use crate::{letter::Letter, word::Word};
+
+  #[cfg(feature = "parse")]
+  #[test]
+  fn letter() {
+    // the error should point at the character that couldn't be parsed
+    let err = "[ab!]".parse::<Letter>().unwrap_err();
+    assert_eq!(err.offset, 3);
+    assert_eq!(err.found, Some('!'));
+    assert_eq!(err.expected, "a letter, `-` or `]`");
+  }
+
+  #[cfg(feature = "parse")]
+  #[test]
+  fn word() {
+    // errors in a word should be offset from the start of the word
+    let err = "ab[cd".parse::<Word>().unwrap_err();
+    assert_eq!(err.offset, 5);
+    assert_eq!(err.found, None);
+    let err = "abc!!".parse::<Word>().unwrap_err();
+    assert_eq!(err.offset, 3);
+    assert_eq!(err.found, Some('!'));
+  }
+
+  #[test]
+  fn caret() {
+    // the caret should sit under the offending character
+    let err = ParseError::new("abc!!", "!!");
+    let expected = "expected a letter, `.` or `[` but found `!` at 3\nabc!!\n   ^";
+    assert_eq!(err.to_string(), expected);
+  }
+}
@@ -1,17 +1,18 @@
 use crate::set::Set;
 
+pub use crate::alphabet::{from_index, from_index_unchecked, into_index, into_index_unchecked};
+pub use counts::{LetterCounts, TextPolicy};
+pub use error::ParseError;
+pub use parse::{Case, ParseOptions, ParsedLetter};
+
+pub mod bits;
+mod counts;
+mod error;
 pub mod parse;
+#[cfg(feature = "rand")]
 mod random;
 pub mod unparse;
 
-pub fn into_index(c: char) -> usize {
-  (c as usize) - ('a' as usize)
-}
-
-pub fn from_index(i: usize) -> char {
-  (i + ('a' as usize)) as u8 as char
-}
-
 // todo: convert to a bitset for performance
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct Letter([bool; 26]);
@@ -21,10 +22,6 @@ impl Letter {
     (0..26).contains(&i) && self.0[i]
   }
 
-  pub(crate) fn has_idx_unchecked(&self, i: usize) -> bool {
-    self.0[i]
-  }
-
   pub(crate) fn peek_idx(&self) -> Option<usize> {
     self.0.iter().position(|&b| b)
   }
@@ -38,7 +35,7 @@ impl Letter {
   }
 
   pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
-    self.indices().map(from_index)
+    self.indices().map(from_index_unchecked)
   }
 }
 
@@ -47,14 +44,14 @@ impl Iterator for Letter {
   fn next(&mut self) -> Option<Self::Item> {
     let idx = self.peek_idx()?;
     self.0[idx] = false;
-    Some(from_index(idx))
+    Some(from_index_unchecked(idx))
   }
 }
 
 impl Extend<char> for Letter {
   fn extend<T: IntoIterator<Item = char>>(&mut self, iter: T) {
     for c in iter {
-      self.0[into_index(c)] = true;
+      self.0[into_index_unchecked(c)] = true;
     }
   }
 }
@@ -70,7 +67,7 @@ impl FromIterator<char> for Letter {
 impl Set for Letter {
   fn singleton(item: Self::Item) -> Self {
     let mut letter = Self::default();
-    letter.0[into_index(item)] = true;
+    letter.0[into_index_unchecked(item)] = true;
     letter
   }
 
@@ -81,7 +78,7 @@ impl Set for Letter {
     self.0 == [false; 26]
   }
   fn contains(&self, item: &Self::Item) -> bool {
-    self.has_idx(into_index(*item))
+    into_index(*item).is_some_and(|i| self.0[i])
   }
   fn subset(&self, other: &Self) -> bool {
     for i in 0..26 {
@@ -93,7 +90,7 @@ impl Set for Letter {
   }
 
   fn insert(&mut self, item: Self::Item) -> bool {
-    let idx = into_index(item);
+    let idx = into_index_unchecked(item);
     let prev = self.0[idx];
     self.0[idx] = true;
     prev
@@ -103,13 +100,13 @@ impl Set for Letter {
     F: FnMut(&Self::Item) -> bool,
   {
     for i in 0..26 {
-      if !f(&from_index(i)) {
+      if !f(&from_index_unchecked(i)) {
         self.0[i] = false;
       }
     }
   }
   fn delete(&mut self, item: &Self::Item) -> bool {
-    let idx = into_index(*item);
+    let idx = into_index_unchecked(*item);
     let prev = self.0[idx];
     self.0[idx] = false;
     prev
@@ -1,15 +1,5 @@
-use super::{into_index, Letter};
-use nom::{
-  branch::alt,
-  bytes::complete::tag,
-  character::complete::{anychar, char},
-  combinator::{map_res, opt, verify},
-  error::Error,
-  multi::many1,
-  sequence::delimited,
-  Finish, IResult,
-};
-use std::{ops::RangeInclusive, str::FromStr};
+use super::{into_index, Letter, ParseError};
+use std::{borrow::Cow, str::FromStr};
 
 impl Letter {
   pub(crate) fn all() -> Self {
@@ -18,78 +8,165 @@ impl Letter {
 
   // todo: `String` is a bad error type, define a better one
   pub(crate) fn try_from_alpha(c: char) -> Result<Self, String> {
-    if !c.is_ascii_lowercase() {
-      return Err(format!("`{c}` is not in a-z"));
-    }
-    let mut mask = [false; 26];
-    mask[into_index(c)] = true;
-    Ok(Self(mask))
-  }
-
-  pub(crate) fn try_from_iter(iter: impl IntoIterator<Item = char>) -> Result<Self, String> {
+    let idx = into_index(c).ok_or_else(|| format!("`{c}` is not in a-z"))?;
     let mut mask = [false; 26];
-    for c in iter {
-      if !c.is_ascii_lowercase() {
-        return Err(format!("`{c}` is not in a-z"));
-      }
-      mask[into_index(c)] = true;
-    }
+    mask[idx] = true;
     Ok(Self(mask))
   }
 }
 
-fn parse_lower_char(input: &str) -> IResult<&str, char> {
-  verify(anychar, char::is_ascii_lowercase)(input)
+/// How the letters of a pattern may be cased.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Case {
+  /// Only lowercase letters are accepted.
+  #[default]
+  Lower,
+  /// Uppercase letters are read as their lowercase counterparts.
+  Insensitive,
+  /// As in GCG files, uppercase letters are natural tiles and lowercase
+  /// letters are designated blanks, both read as lowercase letters.
+  Gcg,
 }
 
-fn parse_dot_letter(input: &str) -> IResult<&str, Letter> {
-  let (input, _) = char('.')(input)?;
-  Ok((input, Letter::all()))
+/// Options for parsing letters and words.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+  pub case: Case,
 }
 
-fn parse_char_letter(input: &str) -> IResult<&str, Letter> {
-  map_res(parse_lower_char, Letter::try_from_alpha)(input)
+impl ParseOptions {
+  /// The input as it should be parsed, with the same byte offsets.
+  pub(crate) fn normalise<'a>(&self, input: &'a str) -> Cow<'a, str> {
+    match self.case {
+      Case::Lower => Cow::Borrowed(input),
+      Case::Insensitive | Case::Gcg => Cow::Owned(input.to_ascii_lowercase()),
+    }
+  }
+
+  /// Whether the text of a letter marks it as a designated blank.
+  pub(crate) fn is_blank(&self, text: &str) -> bool {
+    self.case == Case::Gcg && text.contains(|c: char| c.is_ascii_lowercase())
+  }
 }
 
-fn parse_single_char_range(input: &str) -> IResult<&str, RangeInclusive<char>> {
-  let (input, c) = parse_lower_char(input)?;
-  Ok((input, c..=c))
+/// A letter along with whether it was written as a designated blank, which
+/// is only ever the case with [`Case::Gcg`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParsedLetter {
+  pub letter: Letter,
+  pub blank: bool,
 }
 
-fn parse_multi_char_range(input: &str) -> IResult<&str, RangeInclusive<char>> {
-  let (input, start) = opt(parse_lower_char)(input)?;
-  let (input, _) = tag("-")(input)?;
-  let (input, end) = opt(parse_lower_char)(input)?;
-  Ok((input, start.unwrap_or('a')..=end.unwrap_or('z')))
+impl Letter {
+  /// Parses a single letter with the given options.
+  pub fn parse_with(s: &str, options: &ParseOptions) -> Result<ParsedLetter, ParseError> {
+    let input = options.normalise(s);
+    match parse_letter(&input) {
+      Ok((rest, letter)) => Ok(ParsedLetter {
+        letter,
+        blank: options.is_blank(&s[..s.len() - rest.len()]),
+      }),
+      Err(rest) => Err(ParseError::new(s, &s[s.len() - rest.len()..])),
+    }
+  }
 }
 
-fn parse_char_range(input: &str) -> IResult<&str, RangeInclusive<char>> {
-  alt((parse_multi_char_range, parse_single_char_range))(input)
+/// Parses a single letter from the start of the input, giving back either
+/// the rest of the input or the input it failed at.
+#[cfg(feature = "parse")]
+pub(crate) fn parse_letter(input: &str) -> Result<(&str, Letter), &str> {
+  use nom::{error::Error, Finish};
+  grammar::parse_letter(input)
+    .finish()
+    .map_err(|Error { input, .. }| input)
 }
 
-fn parse_group_letter(input: &str) -> IResult<&str, Letter> {
-  map_res(many1(parse_char_range), |ranges| {
-    Letter::try_from_iter(ranges.into_iter().flatten())
-  })(input)
+/// Without the `parse` feature only `.` and single letters are accepted,
+/// so groups such as `[a-e]` fail at the opening bracket.
+#[cfg(not(feature = "parse"))]
+pub(crate) fn parse_letter(input: &str) -> Result<(&str, Letter), &str> {
+  let mut chars = input.chars();
+  let letter = match chars.next() {
+    Some('.') => Letter::all(),
+    Some(c) => Letter::try_from_alpha(c).map_err(|_| input)?,
+    None => return Err(input),
+  };
+  Ok((chars.as_str(), letter))
 }
 
-pub(crate) fn parse_letter(input: &str) -> IResult<&str, Letter> {
-  alt((
-    parse_dot_letter,
-    parse_char_letter,
-    delimited(char('['), parse_group_letter, char(']')),
-  ))(input)
+/// The full pattern grammar, including `[...]` groups and ranges.
+#[cfg(feature = "parse")]
+mod grammar {
+  use super::{into_index, Letter};
+  use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{anychar, char},
+    combinator::{map_res, opt, verify},
+    multi::many1,
+    sequence::delimited,
+    IResult,
+  };
+  use std::ops::RangeInclusive;
+
+  impl Letter {
+    pub(crate) fn try_from_iter(iter: impl IntoIterator<Item = char>) -> Result<Self, String> {
+      let mut mask = [false; 26];
+      for c in iter {
+        let idx = into_index(c).ok_or_else(|| format!("`{c}` is not in a-z"))?;
+        mask[idx] = true;
+      }
+      Ok(Self(mask))
+    }
+  }
+
+  fn parse_lower_char(input: &str) -> IResult<&str, char> {
+    verify(anychar, char::is_ascii_lowercase)(input)
+  }
+
+  fn parse_dot_letter(input: &str) -> IResult<&str, Letter> {
+    let (input, _) = char('.')(input)?;
+    Ok((input, Letter::all()))
+  }
+
+  fn parse_char_letter(input: &str) -> IResult<&str, Letter> {
+    map_res(parse_lower_char, Letter::try_from_alpha)(input)
+  }
+
+  fn parse_single_char_range(input: &str) -> IResult<&str, RangeInclusive<char>> {
+    let (input, c) = parse_lower_char(input)?;
+    Ok((input, c..=c))
+  }
+
+  fn parse_multi_char_range(input: &str) -> IResult<&str, RangeInclusive<char>> {
+    let (input, start) = opt(parse_lower_char)(input)?;
+    let (input, _) = tag("-")(input)?;
+    let (input, end) = opt(parse_lower_char)(input)?;
+    Ok((input, start.unwrap_or('a')..=end.unwrap_or('z')))
+  }
+
+  fn parse_char_range(input: &str) -> IResult<&str, RangeInclusive<char>> {
+    alt((parse_multi_char_range, parse_single_char_range))(input)
+  }
+
+  fn parse_group_letter(input: &str) -> IResult<&str, Letter> {
+    map_res(many1(parse_char_range), |ranges| {
+      Letter::try_from_iter(ranges.into_iter().flatten())
+    })(input)
+  }
+
+  pub(super) fn parse_letter(input: &str) -> IResult<&str, Letter> {
+    alt((
+      parse_dot_letter,
+      parse_char_letter,
+      delimited(char('['), parse_group_letter, char(']')),
+    ))(input)
+  }
 }
 
 impl FromStr for Letter {
-  type Err = Error<String>;
+  type Err = ParseError;
   fn from_str(s: &str) -> Result<Self, Self::Err> {
-    match parse_letter(s).finish() {
-      Ok((_, l)) => Ok(l),
-      Err(Error { input, code }) => Err(Error {
-        input: input.to_string(),
-        code,
-      }),
-    }
+    Letter::parse_with(s, &ParseOptions::default()).map(|parsed| parsed.letter)
   }
 }
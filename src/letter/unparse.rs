@@ -1,28 +1,26 @@
-use std::{convert::Infallible, fmt::Display};
+use std::{convert::Infallible, fmt::Display, iter};
 
-use super::{from_index, Letter};
+use super::{from_index_unchecked, Letter};
 
 /// A helper function that combines consecutive, ascending numbers into ranges.
 fn combine_into_ranges(
   nums: impl IntoIterator<Item = usize>,
 ) -> impl Iterator<Item = (usize, usize)> {
-  let mut nums = nums.into_iter();
-  let mut range = nums.next().map(|n| (n, n));
-  nums
-    .filter_map(move |n| {
-      if let Some((s, e)) = range {
-        if e + 1 == n {
-          range.replace((s, n));
-          return None;
-        }
-      }
-      range.replace((n, n))
-    })
-    .chain(range)
+  let mut nums = nums.into_iter().peekable();
+  iter::from_fn(move || {
+    let start = nums.next()?;
+    let mut end = start;
+    while let Some(n) = nums.next_if_eq(&(end + 1)) {
+      end = n
+    }
+    Some((start, end))
+  })
 }
 
 // We're going to try to closely mimic nom's parser combinators here.
 
+// the counts are only surfaced through `Debug`
+#[allow(dead_code)]
 #[derive(Debug, Clone)]
 enum ErrorKind {
   TooManyChars(usize),
@@ -49,7 +47,7 @@ fn unparse_char_letter(Letter(mask): &Letter) -> Result<String, ErrorKind> {
   }
 
   let idx = mask.iter().position(|&b| b).unwrap();
-  Ok(from_index(idx).into())
+  Ok(from_index_unchecked(idx).into())
 }
 
 fn unparse_char_pair(&(start, end): &(char, char)) -> Result<String, Infallible> {
@@ -63,7 +61,7 @@ fn unparse_char_pair(&(start, end): &(char, char)) -> Result<String, Infallible>
 fn unparse_group_letter(letter: &Letter) -> Result<String, Infallible> {
   let ranges = combine_into_ranges(letter.indices());
   let ranges_string = ranges
-    .map(|(s, e)| unparse_char_pair(&(from_index(s), from_index(e))))
+    .map(|(s, e)| unparse_char_pair(&(from_index_unchecked(s), from_index_unchecked(e))))
     .collect::<Result<String, _>>()?;
   Ok(format!("[{}]", ranges_string))
 }
@@ -76,6 +74,6 @@ pub(crate) fn unparse_letter(letter: &Letter) -> Result<String, Infallible> {
 
 impl Display for Letter {
   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-    write!(f, "{}", unparse_letter(self).unwrap())
+    f.pad(&unparse_letter(self).unwrap())
   }
 }
@@ -0,0 +1,38 @@
+use crate::letter::Letter;
+
+/// What a study tool knows about a single word in a lexicon.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Entry {
+  pub word: String,
+  pub definition: Option<String>,
+  /// Letters that can be placed before the word to make another word.
+  pub front_hooks: Letter,
+  /// Letters that can be placed after the word to make another word.
+  pub back_hooks: Letter,
+  /// How likely the word is to be playable, as ranked by the source.
+  pub playability: Option<f64>,
+  /// The chance of drawing the word's tiles from a full bag.
+  pub probability: Option<f64>,
+}
+
+impl Entry {
+  /// An entry with no details beyond the word itself.
+  pub fn new(word: &str) -> Self {
+    Self {
+      word: word.to_owned(),
+      ..Default::default()
+    }
+  }
+}
+
+/// Hooks written out as their letters in order, i.e. `cht`.
+#[cfg(any(feature = "csv", feature = "sqlite"))]
+pub(crate) fn hooks_to_string(hooks: &Letter) -> String {
+  hooks.chars().collect()
+}
+
+/// Hooks read back from their letters, skipping anything outside `a-z`.
+#[cfg(any(feature = "csv", feature = "sqlite"))]
+pub(crate) fn hooks_from_str(hooks: &str) -> Letter {
+  hooks.chars().filter(char::is_ascii_lowercase).collect()
+}
@@ -0,0 +1,123 @@
+use crate::trie_ptr::Trie;
+use sha2::{Digest, Sha256};
+use std::{
+  fs,
+  io::{self, Read},
+  path::{Path, PathBuf},
+};
+
+/// The lowercase hex SHA-256 digest of some bytes.
+fn sha256_hex(bytes: &[u8]) -> String {
+  Sha256::digest(bytes)
+    .iter()
+    .map(|b| format!("{b:02x}"))
+    .collect()
+}
+
+/// Checks bytes against an expected hex digest, if there is one.
+fn verify(bytes: &[u8], expected: Option<&str>) -> io::Result<()> {
+  let Some(expected) = expected else {
+    return Ok(());
+  };
+  let actual = sha256_hex(bytes);
+  if actual.eq_ignore_ascii_case(expected) {
+    Ok(())
+  } else {
+    Err(io::Error::new(
+      io::ErrorKind::InvalidData,
+      format!("Checksum mismatch, expected {expected} but got {actual}."),
+    ))
+  }
+}
+
+/// Where a url is cached, keeping its extension so it can be loaded as usual.
+fn cache_path(url: &str, cache_dir: &Path) -> io::Result<PathBuf> {
+  let name = url.split(['?', '#']).next().unwrap_or(url);
+  let ext = Path::new(name)
+    .extension()
+    .and_then(|s| s.to_str())
+    .filter(|ext| ["tre", "txt"].contains(ext))
+    .ok_or_else(|| {
+      io::Error::new(
+        io::ErrorKind::InvalidData,
+        "File type not supported, please fetch either a .tre or .txt file.",
+      )
+    })?;
+  Ok(cache_dir.join(format!("{}.{ext}", sha256_hex(url.as_bytes()))))
+}
+
+/// The default cache directory, falling back to the temp directory
+/// on platforms without a user cache.
+fn default_cache_dir() -> PathBuf {
+  dirs::cache_dir()
+    .unwrap_or_else(std::env::temp_dir)
+    .join("scrabble")
+}
+
+/// Downloads a `.tre` or `.txt` dictionary, caching it for later calls.
+///
+/// If `digest` is given, it should be the hex SHA-256 of the file, and both
+/// cached and downloaded files are checked against it. A cached file that
+/// doesn't match is downloaded again.
+pub fn fetch(url: &str, digest: Option<&str>) -> io::Result<Trie> {
+  fetch_into(url, digest, &default_cache_dir())
+}
+
+/// The same as [`fetch`], but caching into the given directory.
+pub fn fetch_into(url: &str, digest: Option<&str>, cache_dir: &Path) -> io::Result<Trie> {
+  let path = cache_path(url, cache_dir)?;
+  let cached = fs::read(&path).ok();
+  if cached.is_some_and(|bytes| verify(&bytes, digest).is_ok()) {
+    return Trie::file(&path);
+  }
+
+  let mut bytes = vec![];
+  ureq::get(url)
+    .call()
+    .map_err(io::Error::other)?
+    .into_reader()
+    .read_to_end(&mut bytes)?;
+  verify(&bytes, digest)?;
+
+  fs::create_dir_all(cache_dir)?;
+  fs::write(&path, bytes)?;
+  Trie::file(&path)
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn digest() {
+    // digests should be checked case insensitively
+    let hash = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+    assert_eq!(sha256_hex(b"abc"), hash);
+    assert!(verify(b"abc", Some(&hash.to_uppercase())).is_ok());
+    assert!(verify(b"abd", Some(hash)).is_err());
+    assert!(verify(b"abd", None).is_ok());
+  }
+
+  #[test]
+  fn extension() {
+    // only urls for files we can load should be accepted
+    let dir = Path::new("cache");
+    let path = cache_path("https://example.com/words.txt?v=2", dir).unwrap();
+    assert_eq!(path.extension().unwrap(), "txt");
+    assert!(cache_path("https://example.com/words.zip", dir).is_err());
+  }
+
+  #[test]
+  fn cached() {
+    // a cached file should be used without going to the network
+    let dir = crate::temp_path("scrabble_fetch_cached");
+    let url = "http://localhost:0/words.txt";
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(cache_path(url, &dir).unwrap(), "cat\ndog\n").unwrap();
+
+    let digest = sha256_hex(b"cat\ndog\n");
+    let trie = fetch_into(url, Some(&digest), &dir);
+    fs::remove_dir_all(&dir).unwrap();
+    assert!(trie.unwrap().has("dog"));
+  }
+}
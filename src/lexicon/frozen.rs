@@ -0,0 +1,66 @@
+use crate::{trie::TrieNode, trie_ptr::Trie, word::Word};
+use std::{ops::Deref, sync::Arc};
+
+/// A dictionary that can no longer change, for sharing between threads.
+///
+/// It only hands out shared references to its trie, which has no interior
+/// mutability, so holding a `&FrozenTrie` is enough to query it from any
+/// number of threads. Clones share the same nodes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrozenTrie(Arc<Trie>);
+
+impl FrozenTrie {
+  /// Freezes a copy of the words in any kind of trie.
+  pub fn new<N: TrieNode>(node: &N) -> Self {
+    let mut trie: Trie = node.strings().collect();
+    if node.is_end() {
+      trie.set_end(true)
+    }
+    Self(Arc::new(trie))
+  }
+
+  /// The words matching a pattern, in alphabetical order.
+  pub fn matches(&self, pattern: Word) -> Vec<String> {
+    (&*self.0 & Trie::word(pattern)).collect()
+  }
+}
+
+impl From<Trie> for FrozenTrie {
+  fn from(trie: Trie) -> Self {
+    Self(Arc::new(trie))
+  }
+}
+
+impl Deref for FrozenTrie {
+  type Target = Trie;
+  fn deref(&self) -> &Self::Target {
+    &self.0
+  }
+}
+
+#[cfg(all(test, feature = "parse"))]
+mod test {
+  use super::*;
+  use std::thread;
+
+  #[test]
+  fn from_node() {
+    // any trie node should freeze to the same words
+    let trie = Trie::str("c[ao]t");
+    let frozen = FrozenTrie::new(&trie);
+    assert_eq!(*frozen, trie);
+    assert_eq!(frozen.matches("c[a-z]t".parse().unwrap()), ["cat", "cot"]);
+  }
+
+  #[test]
+  fn threads() {
+    // queries should be possible from several threads at once
+    let frozen = FrozenTrie::from(Trie::str("c[ao]t"));
+    thread::scope(|s| {
+      for word in ["cat", "cot"] {
+        let frozen = &frozen;
+        s.spawn(move || assert!(frozen.has(word)));
+      }
+    });
+  }
+}
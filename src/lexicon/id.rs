@@ -0,0 +1,172 @@
+use crate::trie_ptr::Metadata;
+use std::{error::Error, fmt::Display, str::FromStr};
+
+/// Which word list a lexicon is, so that results can be checked against
+/// the lexicon they were produced with.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LexiconId {
+  /// The NASPA Word List, 2023 edition.
+  Nwl23,
+  /// Collins Scrabble Words, 2021 edition.
+  Csw21,
+  /// Any other word list, identified by its name and a hash of its words.
+  ///
+  /// The names of the known lexicons are reserved and hashes can't contain
+  /// `#`, so use [`LexiconId::custom`] to make sure the id round trips.
+  Custom { name: String, hash: String },
+}
+
+impl LexiconId {
+  /// A known lexicon from its name, ignoring case.
+  fn known(name: &str) -> Option<Self> {
+    match name.to_ascii_uppercase().as_str() {
+      "NWL23" => Some(LexiconId::Nwl23),
+      "CSW21" => Some(LexiconId::Csw21),
+      _ => None,
+    }
+  }
+
+  /// Identifies any other word list, failing if the name is empty or
+  /// reserved for a known lexicon, or the hash is empty or contains `#`.
+  pub fn custom(name: &str, hash: &str) -> Result<Self, UnknownLexicon> {
+    let reserved = Self::known(name).is_some();
+    if name.is_empty() || reserved || hash.is_empty() || hash.contains('#') {
+      return Err(UnknownLexicon(format!("{name}#{hash}")));
+    }
+    Ok(LexiconId::Custom {
+      name: name.to_owned(),
+      hash: hash.to_owned(),
+    })
+  }
+
+  /// Checks that a lexicon is the one expected, such as the lexicon a game
+  /// was played under, before analysing anything with it.
+  pub fn expect(&self, found: &LexiconId) -> Result<(), LexiconMismatch> {
+    match self == found {
+      true => Ok(()),
+      false => Err(LexiconMismatch {
+        expected: self.clone(),
+        found: found.clone(),
+      }),
+    }
+  }
+}
+
+impl Display for LexiconId {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      LexiconId::Nwl23 => write!(f, "NWL23"),
+      LexiconId::Csw21 => write!(f, "CSW21"),
+      LexiconId::Custom { name, hash } => write!(f, "{name}#{hash}"),
+    }
+  }
+}
+
+impl FromStr for LexiconId {
+  type Err = UnknownLexicon;
+  /// Parses the names of known lexicons, ignoring case, or `name#hash`,
+  /// where the name may contain `#` but the hash can't.
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    if let Some(id) = Self::known(s) {
+      return Ok(id);
+    }
+    let (name, hash) = s
+      .rsplit_once('#')
+      .ok_or_else(|| UnknownLexicon(s.to_owned()))?;
+    Self::custom(name, hash)
+  }
+}
+
+impl Metadata {
+  /// The lexicon a saved trie holds, from its name and source hash.
+  ///
+  /// Names of known lexicons are recognised whatever their hash, whilst
+  /// anything else needs both a name and a hash to be identified.
+  pub fn lexicon_id(&self) -> Option<LexiconId> {
+    let name = self.name.as_deref()?;
+    LexiconId::known(name).or_else(|| {
+      let hash = self.source_sha256.as_deref()?;
+      LexiconId::custom(name, hash).ok()
+    })
+  }
+}
+
+/// A name that isn't a known lexicon or written as `name#hash`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownLexicon(pub String);
+
+impl Display for UnknownLexicon {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "`{}` isn't a known lexicon or `name#hash`", self.0)
+  }
+}
+
+impl Error for UnknownLexicon {}
+
+/// A lexicon that differs from the one expected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LexiconMismatch {
+  pub expected: LexiconId,
+  pub found: LexiconId,
+}
+
+impl Display for LexiconMismatch {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(
+      f,
+      "expected lexicon {} but found {}",
+      self.expected, self.found
+    )
+  }
+}
+
+impl Error for LexiconMismatch {}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn round_trip() {
+    // ids should parse back from how they're displayed
+    let custom = LexiconId::Custom {
+      name: "Club".into(),
+      hash: "ab12".into(),
+    };
+    for id in [LexiconId::Nwl23, LexiconId::Csw21, custom] {
+      assert_eq!(id.to_string().parse(), Ok(id));
+    }
+    assert_eq!("csw21".parse(), Ok(LexiconId::Csw21));
+    assert!("Club".parse::<LexiconId>().is_err());
+    // names may contain `#`, but can't be a known lexicon's
+    let sharp = LexiconId::custom("Club #2", "ab12").unwrap();
+    assert_eq!(sharp.to_string().parse(), Ok(sharp));
+    assert!(LexiconId::custom("nwl23", "ab12").is_err());
+    assert!("nwl23#ab12".parse::<LexiconId>().is_err());
+    assert!(LexiconId::custom("Club", "ab#12").is_err());
+  }
+
+  #[test]
+  fn from_metadata() {
+    let mut metadata = Metadata {
+      name: Some("NWL23".into()),
+      ..Default::default()
+    };
+    assert_eq!(metadata.lexicon_id(), Some(LexiconId::Nwl23));
+    metadata.name = Some("Club".into());
+    assert_eq!(metadata.lexicon_id(), None);
+    metadata.source_sha256 = Some("ab12".into());
+    assert_eq!(metadata.lexicon_id(), Some("Club#ab12".parse().unwrap()));
+  }
+
+  #[test]
+  fn expect() {
+    // mismatched lexicons should be refused
+    assert!(LexiconId::Nwl23.expect(&LexiconId::Nwl23).is_ok());
+    let mismatch = LexiconId::Nwl23.expect(&LexiconId::Csw21).unwrap_err();
+    assert_eq!(
+      mismatch.to_string(),
+      "expected lexicon NWL23 but found CSW21"
+    );
+  }
+}
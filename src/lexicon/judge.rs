@@ -0,0 +1,66 @@
+use crate::trie_ptr::Trie;
+use std::fmt::Display;
+
+/// The outcome of judging the words formed by a play.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Judgement {
+  /// Each word as it was given, with whether it's in the lexicon.
+  pub words: Vec<(String, bool)>,
+}
+
+impl Judgement {
+  /// Whether the play stands, which needs every word to be valid.
+  pub fn is_valid(&self) -> bool {
+    !self.words.is_empty() && self.words.iter().all(|&(_, valid)| valid)
+  }
+
+  /// The words that aren't in the lexicon.
+  pub fn invalid(&self) -> impl Iterator<Item = &str> + '_ {
+    let invalid = self.words.iter().filter(|(_, valid)| !valid);
+    invalid.map(|(word, _)| word.as_str())
+  }
+}
+
+impl Display for Judgement {
+  /// Only gives the verdict on the play as a whole, like a word judge, so
+  /// that a challenge doesn't reveal which of the words was at fault.
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let words: Vec<_> = self.words.iter().map(|(w, _)| w.to_uppercase()).collect();
+    let verdict = match self.is_valid() {
+      true => "VALID",
+      false => "NOT VALID",
+    };
+    write!(f, "{}: the play is {verdict}", words.join(", "))
+  }
+}
+
+/// Judges the words formed by a play, which is only valid if all of them
+/// are in the lexicon. Words are looked up ignoring case.
+pub fn judge(lexicon: &Trie, words: &[&str]) -> Judgement {
+  let words = words.iter().map(|&word| {
+    let valid = lexicon.contains_str(&word.to_ascii_lowercase());
+    (word.to_owned(), valid)
+  });
+  Judgement {
+    words: words.collect(),
+  }
+}
+
+#[cfg(all(test, feature = "parse"))]
+mod test {
+  use super::*;
+
+  #[test]
+  fn all_or_nothing() {
+    // one invalid word should reject the whole play
+    let lexicon = Trie::str("c[ao]t");
+    let judgement = judge(&lexicon, &["CAT", "cot"]);
+    assert!(judgement.is_valid());
+    assert_eq!(judgement.to_string(), "CAT, COT: the play is VALID");
+
+    let judgement = judge(&lexicon, &["cat", "cut", "c[ao]t"]);
+    assert!(!judgement.is_valid());
+    assert_eq!(judgement.invalid().collect::<Vec<_>>(), ["cut", "c[ao]t"]);
+    assert!(!judge(&lexicon, &[]).is_valid());
+  }
+}
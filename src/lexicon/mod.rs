@@ -0,0 +1,27 @@
+/*!
+Dictionaries as used by long-running tools, rather than single queries.
+*/
+
+pub(crate) mod entry;
+#[cfg(feature = "net")]
+mod fetch;
+mod frozen;
+mod id;
+mod judge;
+#[cfg(feature = "pipeline")]
+mod pipeline;
+mod shared;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+
+pub use entry::Entry;
+#[cfg(feature = "net")]
+pub use fetch::{fetch, fetch_into};
+pub use frozen::FrozenTrie;
+pub use id::{LexiconId, LexiconMismatch, UnknownLexicon};
+pub use judge::{judge, Judgement};
+#[cfg(feature = "pipeline")]
+pub use pipeline::{Built, Filter, MetadataSpec, Output, Pipeline, PipelineError};
+pub use shared::SharedLexicon;
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteLexicon;
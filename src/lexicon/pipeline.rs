@@ -0,0 +1,419 @@
+/*!
+Building a lexicon from word lists in a fixed series of steps, configured
+by a small TOML spec rather than a custom program:
+
+```toml
+sources = ["words.txt", "extra.tre"]
+exclude = ["offensive.txt"]
+base = "previous.tre"
+definitions = "definitions.csv"
+
+[filter]
+min_len = 2
+max_len = 15
+
+[metadata]
+name = "Example"
+version = "2024"
+
+[output]
+tre = "lexicon.tre"
+dawg = "lexicon.dawg"
+definitions = "lexicon.csv"
+```
+
+The steps run in order:
+1. Load and union the sources, normalising `.txt` words by trimming and
+   lowercasing them, and skipping any that still aren't in `a-z`.
+2. Remove any words in the `exclude` lists.
+3. Filter by length and by a pattern, such as `.....` for five letters.
+4. Diff against the `base` lexicon, reporting the words added and removed.
+5. Attach definitions from a CSV file, with the `csv` feature.
+6. Write the `.tre` with its metadata, the minimised dawg with the `dawg`
+   feature, and the definitions with the `csv` feature.
+
+Relative paths in a spec file are relative to the spec itself, and specs
+using definitions or a dawg fail to run without the features they need.
+*/
+use crate::{
+  trie::TrieNode,
+  trie_ptr::{front_coding, Metadata, Trie},
+  word::Word,
+};
+use serde::Deserialize;
+use std::{
+  error::Error,
+  fmt::Display,
+  fs::{self, File},
+  io::{self, BufRead, BufReader},
+  path::{Path, PathBuf},
+};
+
+/// A reason a lexicon couldn't be built.
+#[derive(Debug)]
+pub enum PipelineError {
+  /// A source couldn't be read or an output couldn't be written.
+  Io(PathBuf, io::Error),
+  /// The spec isn't valid TOML or is missing fields.
+  Spec(toml::de::Error),
+  /// The pattern to filter by isn't a valid pattern.
+  Pattern(crate::word::ParseError),
+  /// The definitions couldn't be read or written.
+  #[cfg(feature = "csv")]
+  Csv(PathBuf, crate::formats::csv::CsvError),
+  /// The spec sets a field that needs a feature this build doesn't have.
+  Unsupported {
+    field: &'static str,
+    feature: &'static str,
+  },
+}
+
+impl Display for PipelineError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::Io(path, err) => write!(f, "{}: {err}", path.display()),
+      Self::Spec(err) => write!(f, "invalid spec: {err}"),
+      Self::Pattern(err) => write!(f, "invalid pattern: {err}"),
+      #[cfg(feature = "csv")]
+      Self::Csv(path, err) => write!(f, "{}: {err}", path.display()),
+      Self::Unsupported { field, feature } => {
+        write!(f, "`{field}` needs the `{feature}` feature")
+      }
+    }
+  }
+}
+
+impl Error for PipelineError {}
+
+/// The words to keep by their length and shape.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Filter {
+  pub min_len: Option<usize>,
+  pub max_len: Option<usize>,
+  /// Only words matching this pattern are kept.
+  pub pattern: Option<String>,
+}
+
+/// Where to write the built lexicon.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Output {
+  pub tre: Option<PathBuf>,
+  /// The lexicon as a minimised dawg, see [`crate::dawg::Dawg::to_bytes`].
+  pub dawg: Option<PathBuf>,
+  /// The definitions of the words in the lexicon, as CSV.
+  pub definitions: Option<PathBuf>,
+}
+
+/// The metadata written with the `.tre`, see [`Metadata`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MetadataSpec {
+  pub name: Option<String>,
+  pub version: Option<String>,
+  pub license: Option<String>,
+}
+
+/// The steps to build a lexicon, see the [module docs](self).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Pipeline {
+  pub sources: Vec<PathBuf>,
+  pub exclude: Vec<PathBuf>,
+  pub filter: Filter,
+  pub base: Option<PathBuf>,
+  pub definitions: Option<PathBuf>,
+  pub metadata: MetadataSpec,
+  pub output: Output,
+  /// The directory relative paths are resolved against.
+  #[serde(skip)]
+  pub root: PathBuf,
+}
+
+/// The result of running a pipeline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Built {
+  pub lexicon: Trie,
+  /// Words in the lexicon but not the base, if there was one.
+  pub added: Option<Trie>,
+  /// Words in the base but not the lexicon, if there was one.
+  pub removed: Option<Trie>,
+}
+
+/// Reads a source, normalising `.txt` words so they can be held by a trie.
+fn load_source(path: &Path) -> io::Result<Trie> {
+  if path.extension().and_then(|s| s.to_str()) != Some("txt") {
+    return Trie::file(path);
+  }
+  let mut trie = Trie::empty();
+  for word in front_coding::words(BufReader::new(File::open(path)?).lines()) {
+    let word = word?.trim().to_ascii_lowercase();
+    if !word.is_empty() && word.chars().all(|c| c.is_ascii_lowercase()) {
+      trie += word.as_str();
+    }
+  }
+  Ok(trie)
+}
+
+impl Pipeline {
+  /// Reads a spec from TOML, resolving paths against the current directory.
+  pub fn from_toml(spec: &str) -> Result<Self, PipelineError> {
+    toml::from_str(spec).map_err(PipelineError::Spec)
+  }
+
+  /// Reads a spec file, resolving paths against its directory.
+  pub fn file(path: &Path) -> Result<Self, PipelineError> {
+    let spec = fs::read_to_string(path).map_err(|err| PipelineError::Io(path.into(), err))?;
+    let mut pipeline = Self::from_toml(&spec)?;
+    pipeline.root = path.parent().unwrap_or(Path::new("")).to_owned();
+    Ok(pipeline)
+  }
+
+  fn path(&self, path: &Path) -> PathBuf {
+    self.root.join(path)
+  }
+
+  fn load_all(&self, paths: &[PathBuf]) -> Result<Trie, PipelineError> {
+    let mut trie = Trie::empty();
+    for path in paths.iter().map(|path| self.path(path)) {
+      trie |= load_source(&path).map_err(|err| PipelineError::Io(path, err))?;
+    }
+    Ok(trie)
+  }
+
+  fn filter(&self, lexicon: &mut Trie) -> Result<(), PipelineError> {
+    let Filter {
+      min_len,
+      max_len,
+      pattern,
+    } = &self.filter;
+    if let Some(pattern) = pattern {
+      let pattern: Word = pattern.parse().map_err(PipelineError::Pattern)?;
+      *lexicon &= Trie::word(pattern);
+    }
+    let min_len = min_len.unwrap_or(0);
+    let max_len = max_len.unwrap_or(usize::MAX);
+    lexicon.retain(|word| (min_len..=max_len).contains(&word.len()));
+    Ok(())
+  }
+
+  /// Fails on any field this build would otherwise have to skip.
+  fn check_features(&self) -> Result<(), PipelineError> {
+    let (dawg, csv) = (cfg!(feature = "dawg"), cfg!(feature = "csv"));
+    let fields = [
+      ("output.dawg", "dawg", dawg, &self.output.dawg),
+      ("definitions", "csv", csv, &self.definitions),
+      ("output.definitions", "csv", csv, &self.output.definitions),
+    ];
+    let missing = fields
+      .into_iter()
+      .find(|(_, _, enabled, path)| path.is_some() && !enabled);
+    match missing {
+      Some((field, feature, ..)) => Err(PipelineError::Unsupported { field, feature }),
+      None => Ok(()),
+    }
+  }
+
+  /// Runs each step in turn, writing any outputs.
+  ///
+  /// Fails before doing anything if the spec sets a field whose feature
+  /// isn't enabled.
+  pub fn run(&self) -> Result<Built, PipelineError> {
+    self.check_features()?;
+    let mut lexicon = self.load_all(&self.sources)?;
+    lexicon /= self.load_all(&self.exclude)?;
+    self.filter(&mut lexicon)?;
+
+    let (added, removed) = match &self.base {
+      Some(base) => {
+        let base = self.load_all(std::slice::from_ref(base))?;
+        (Some(&lexicon / base.clone()), Some(&base / lexicon.clone()))
+      }
+      None => (None, None),
+    };
+
+    self.write(&lexicon)?;
+    Ok(Built {
+      lexicon,
+      added,
+      removed,
+    })
+  }
+
+  fn write(&self, lexicon: &Trie) -> Result<(), PipelineError> {
+    if let Some(path) = &self.output.tre {
+      let path = self.path(path);
+      let MetadataSpec {
+        name,
+        version,
+        license,
+      } = self.metadata.clone();
+      let metadata = Metadata {
+        name,
+        version,
+        license,
+        source_sha256: None,
+      };
+      let written = match metadata == Metadata::default() {
+        true => lexicon.save(&path),
+        false => lexicon.save_with_metadata(&path, &metadata),
+      };
+      written.map_err(|err| PipelineError::Io(path, err))?;
+    }
+
+    #[cfg(feature = "dawg")]
+    if let Some(path) = &self.output.dawg {
+      let path = self.path(path);
+      let dawg = crate::dawg::DawgRoot::from_node(lexicon);
+      fs::write(&path, dawg.to_bytes()).map_err(|err| PipelineError::Io(path, err))?;
+    }
+
+    #[cfg(feature = "csv")]
+    self.write_definitions(lexicon)?;
+    Ok(())
+  }
+
+  /// Attaches definitions and hooks to each word in the lexicon.
+  #[cfg(feature = "csv")]
+  fn write_definitions(&self, lexicon: &Trie) -> Result<(), PipelineError> {
+    use crate::{formats::csv, lexicon::Entry, trie_map::TrieMap};
+    let Some(out) = &self.output.definitions else {
+      return Ok(());
+    };
+    let definitions = match &self.definitions {
+      Some(path) => {
+        let path = self.path(path);
+        csv::load(&path).map_err(|err| PipelineError::Csv(path, err))?
+      }
+      None => TrieMap::new(),
+    };
+
+    let entries = lexicon.annotated_words().map(|annotated| {
+      let known = definitions.get(&annotated.word);
+      let entry = Entry {
+        front_hooks: annotated.front,
+        back_hooks: annotated.back,
+        ..known
+          .cloned()
+          .unwrap_or_else(|| Entry::new(&annotated.word))
+      };
+      (annotated.word, entry)
+    });
+    let out = self.path(out);
+    csv::save(&out, &entries.collect()).map_err(|err| PipelineError::Csv(out, err))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn build() {
+    // each step should apply in turn, with paths relative to the spec
+    let dir = crate::temp_path("scrabble_pipeline");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("words.txt"), " Cat\ncats\ndog\nzzz\nc-t\nox\n").unwrap();
+    fs::write(dir.join("exclude.txt"), "zzz\n").unwrap();
+    let base: Trie = ["cat", "cot"].into_iter().collect();
+    base.save(&dir.join("base.tre")).unwrap();
+    fs::write(
+      dir.join("build.toml"),
+      "sources = [\"words.txt\"]
+exclude = [\"exclude.txt\"]
+base = \"base.tre\"
+
+[filter]
+min_len = 3
+max_len = 3
+
+[metadata]
+name = \"Test\"
+
+[output]
+tre = \"out.tre\"
+",
+    )
+    .unwrap();
+
+    let built = Pipeline::file(&dir.join("build.toml")).unwrap().run();
+    let written = Trie::file(&dir.join("out.tre"));
+    let metadata = Trie::metadata(&dir.join("out.tre"));
+    fs::remove_dir_all(&dir).unwrap();
+
+    let built = built.unwrap();
+    assert_eq!(built.lexicon.strings().collect::<Vec<_>>(), ["cat", "dog"]);
+    assert_eq!(built.added.unwrap(), Trie::str("dog"));
+    assert_eq!(built.removed.unwrap(), Trie::str("cot"));
+    assert_eq!(written.unwrap(), built.lexicon);
+    assert_eq!(metadata.unwrap().unwrap().name.as_deref(), Some("Test"));
+  }
+
+  #[test]
+  fn bad_spec() {
+    // unknown keys are more likely typos than anything else
+    let err = Pipeline::from_toml("sorces = []").unwrap_err();
+    assert!(matches!(err, PipelineError::Spec(_)));
+    let pattern = Pipeline {
+      filter: Filter {
+        pattern: Some("[".into()),
+        ..Default::default()
+      },
+      ..Default::default()
+    };
+    assert!(matches!(pattern.run(), Err(PipelineError::Pattern(_))));
+  }
+
+  #[test]
+  fn unsupported() {
+    // outputs this build can't write should fail rather than be skipped
+    let dawg = Pipeline {
+      output: Output {
+        dawg: Some("out.dawg".into()),
+        ..Default::default()
+      },
+      ..Default::default()
+    };
+    assert_eq!(dawg.check_features().is_ok(), cfg!(feature = "dawg"));
+    let definitions = Pipeline {
+      definitions: Some("defs.csv".into()),
+      ..Default::default()
+    };
+    assert_eq!(definitions.check_features().is_ok(), cfg!(feature = "csv"));
+  }
+
+  #[test]
+  #[cfg(all(feature = "csv", feature = "dawg"))]
+  fn outputs() {
+    // definitions should be kept for words still in the lexicon
+    let dir = crate::temp_path("scrabble_pipeline_outputs");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("words.txt"), "at\ncat\n").unwrap();
+    let definitions = "word,definition\nat,in or near\ndog,a canine\n";
+    fs::write(dir.join("defs.csv"), definitions).unwrap();
+    let pipeline = Pipeline {
+      sources: vec!["words.txt".into()],
+      definitions: Some("defs.csv".into()),
+      output: Output {
+        tre: None,
+        dawg: Some("out.dawg".into()),
+        definitions: Some("out.csv".into()),
+      },
+      root: dir.clone(),
+      ..Default::default()
+    };
+    let built = pipeline.run();
+    let dawg = fs::read(dir.join("out.dawg"));
+    let definitions = fs::read_to_string(dir.join("out.csv"));
+    fs::remove_dir_all(&dir).unwrap();
+
+    built.unwrap();
+    let dawg = crate::dawg::DawgRoot::from_bytes(&dawg.unwrap()).unwrap();
+    assert_eq!(dawg.stats().words, 2);
+    assert_eq!(
+      definitions.unwrap(),
+      "word,definition,front_hooks,back_hooks,probability\nat,in or near,c,,\ncat,,,,\n"
+    );
+  }
+}
@@ -0,0 +1,228 @@
+use super::FrozenTrie;
+use crate::trie_ptr::Trie;
+use std::{
+  io,
+  path::Path,
+  sync::{Arc, Mutex, MutexGuard, PoisonError, RwLock},
+  thread,
+};
+
+type Listener = Arc<dyn Fn(&Trie) + Send + Sync>;
+
+#[derive(Default)]
+struct Listeners {
+  listeners: Vec<Listener>,
+  /// Whether a thread is calling the listeners.
+  notifying: bool,
+  /// Whether there's been a swap since the listeners were last called.
+  pending: bool,
+}
+
+fn lock(listeners: &Mutex<Listeners>) -> MutexGuard<'_, Listeners> {
+  listeners.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Lets the next swap notify again if a listener panics.
+struct Notifying<'a>(&'a Mutex<Listeners>);
+
+impl Drop for Notifying<'_> {
+  fn drop(&mut self) {
+    if thread::panicking() {
+      lock(self.0).notifying = false
+    }
+  }
+}
+
+/// A handle to a dictionary that can be swapped out whilst in use.
+///
+/// Clones of the handle all refer to the same dictionary, so a service can
+/// hand out clones to its workers and call [`reload_from`] on any of them.
+/// Queries in flight keep using whichever trie they started with.
+///
+/// [`reload_from`]: SharedLexicon::reload_from
+#[derive(Clone)]
+pub struct SharedLexicon {
+  trie: Arc<RwLock<Trie>>,
+  listeners: Arc<Mutex<Listeners>>,
+}
+
+impl SharedLexicon {
+  pub fn new(trie: Trie) -> Self {
+    Self {
+      trie: Arc::new(RwLock::new(trie)),
+      listeners: Arc::default(),
+    }
+  }
+
+  pub fn file(path: &Path) -> io::Result<Self> {
+    Ok(Self::new(Trie::file(path)?))
+  }
+
+  /// A snapshot of the current dictionary.
+  ///
+  /// This is cheap, as the snapshot shares all of its nodes with the
+  /// dictionary, and isn't affected by any later reloads.
+  pub fn snapshot(&self) -> Trie {
+    // a poisoned lock still holds a whole trie, as we only ever swap it
+    let trie = self.trie.read().unwrap_or_else(PoisonError::into_inner);
+    trie.snapshot()
+  }
+
+  /// A frozen snapshot of the current dictionary, to share between threads.
+  pub fn frozen(&self) -> FrozenTrie {
+    self.snapshot().into()
+  }
+
+  /// Whether the current dictionary contains a word.
+  pub fn has(&self, word: &str) -> bool {
+    let trie = self.trie.read().unwrap_or_else(PoisonError::into_inner);
+    trie.has(word)
+  }
+
+  /// Swaps in a new dictionary, returning the old one.
+  ///
+  /// Listeners are notified after the swap, once the lock has been released.
+  pub fn replace(&self, trie: Trie) -> Trie {
+    let old = {
+      let mut current = self.trie.write().unwrap_or_else(PoisonError::into_inner);
+      std::mem::replace(&mut *current, trie)
+    };
+    self.notify();
+    old
+  }
+
+  /// Calls the listeners with the current dictionary.
+  ///
+  /// Only one thread calls the listeners at a time, without holding any
+  /// lock, so listeners can swap the dictionary or add listeners too. A
+  /// swap made whilst they're being called is left for that thread to pick
+  /// up once they're done, so they always end on the latest dictionary.
+  fn notify(&self) {
+    {
+      let mut state = lock(&self.listeners);
+      state.pending = true;
+      if state.notifying {
+        return;
+      }
+      state.notifying = true;
+    }
+
+    let _notifying = Notifying(&self.listeners);
+    loop {
+      let listeners = {
+        let mut state = lock(&self.listeners);
+        if !state.pending {
+          state.notifying = false;
+          return;
+        }
+        state.pending = false;
+        state.listeners.clone()
+      };
+      let snapshot = self.snapshot();
+      for listener in &listeners {
+        listener(&snapshot)
+      }
+    }
+  }
+
+  /// Loads a `.tre` or `.txt` file and swaps it in as the dictionary.
+  ///
+  /// The file is loaded before taking the lock, so readers are only blocked
+  /// for the swap itself. The current dictionary is kept if loading fails.
+  pub fn reload_from(&self, path: &Path) -> io::Result<()> {
+    self.replace(Trie::file(path)?);
+    Ok(())
+  }
+
+  /// Registers a function to call with the new dictionary after each swap.
+  ///
+  /// Swaps in quick succession may be reported together, as a single call
+  /// with the latest dictionary.
+  pub fn on_change<F: Fn(&Trie) + Send + Sync + 'static>(&self, listener: F) {
+    lock(&self.listeners).listeners.push(Arc::new(listener))
+  }
+}
+
+impl Default for SharedLexicon {
+  fn default() -> Self {
+    Self::new(Trie::empty())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use std::{
+    fs,
+    sync::atomic::{AtomicUsize, Ordering},
+  };
+
+  #[test]
+  fn replace() {
+    // replacing the dictionary should be visible through every handle
+    let lexicon = SharedLexicon::new(Trie::str("cat"));
+    let handle = lexicon.clone();
+    let old = lexicon.replace(Trie::str("dog"));
+    assert!(old.has("cat"));
+    assert!(handle.has("dog"));
+    assert!(!handle.has("cat"));
+  }
+
+  #[test]
+  fn snapshot() {
+    // snapshots should keep the dictionary they were taken from
+    let lexicon = SharedLexicon::new(Trie::str("cat"));
+    let snapshot = lexicon.snapshot();
+    lexicon.replace(Trie::str("dog"));
+    assert!(snapshot.has("cat"));
+  }
+
+  #[test]
+  fn listeners() {
+    // listeners should be told about every swap
+    let lexicon = SharedLexicon::default();
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    lexicon.on_change(move |trie| {
+      assert!(trie.has("dog"));
+      counter.fetch_add(1, Ordering::SeqCst);
+    });
+    lexicon.replace(Trie::str("dog"));
+    lexicon.replace(Trie::str("dog"));
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+  }
+
+  #[test]
+  fn reentrant_listeners() {
+    // listeners should be able to add listeners and swap the dictionary
+    let lexicon = SharedLexicon::default();
+    let handle = lexicon.clone();
+    let seen = Arc::new(Mutex::new(vec![]));
+    let log = seen.clone();
+    lexicon.on_change(move |trie| {
+      log.lock().unwrap().push(trie.has("cat"));
+      if trie.has("dog") {
+        handle.on_change(|_| {});
+        handle.replace(Trie::str("cat"));
+      }
+    });
+    lexicon.replace(Trie::str("dog"));
+    assert!(lexicon.has("cat"));
+    assert_eq!(*seen.lock().unwrap(), [false, true]);
+    assert_eq!(lock(&lexicon.listeners).listeners.len(), 2);
+  }
+
+  #[test]
+  fn reload_from() {
+    // reloading should pick up the new file, and keep the old trie on error
+    let path = crate::temp_path("scrabble_shared_lexicon_reload.txt");
+    fs::write(&path, "cat\ndog\n").unwrap();
+    let lexicon = SharedLexicon::default();
+    lexicon.reload_from(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert!(lexicon.has("dog"));
+
+    assert!(lexicon.reload_from(&path).is_err());
+    assert!(lexicon.has("cat"));
+  }
+}
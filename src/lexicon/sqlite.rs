@@ -0,0 +1,248 @@
+/*!
+Lexicons stored in a SQLite database, as study tools such as Zyzzyva do,
+so that definitions and the like can live alongside the words.
+
+Everything is kept in a single `words` table, keyed on the word:
+
+| column        | type                  |
+|---------------|-----------------------|
+| `word`        | `TEXT PRIMARY KEY`    |
+| `definition`  | `TEXT`                |
+| `front_hooks` | `TEXT`, i.e. `cht`    |
+| `back_hooks`  | `TEXT`                |
+| `playability` | `REAL`, indexed       |
+| `probability` | `REAL`                |
+
+Databases made before a column was added are given it when opened.
+*/
+use super::{
+  entry::{hooks_from_str, hooks_to_string},
+  Entry,
+};
+use crate::trie_ptr::Trie;
+use rusqlite::{params, Connection, OptionalExtension, Result, Row};
+use std::path::Path;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS words (
+  word TEXT PRIMARY KEY NOT NULL,
+  definition TEXT,
+  front_hooks TEXT NOT NULL DEFAULT '',
+  back_hooks TEXT NOT NULL DEFAULT '',
+  playability REAL,
+  probability REAL
+);
+CREATE INDEX IF NOT EXISTS words_by_playability ON words (playability);
+";
+
+/// Columns added since the table was first made, along with how to add
+/// them to an older database.
+const MIGRATIONS: &[(&str, &str)] = &[(
+  "probability",
+  "ALTER TABLE words ADD COLUMN probability REAL",
+)];
+
+/// A lexicon held in a SQLite database, see the [module docs](self).
+pub struct SqliteLexicon {
+  conn: Connection,
+}
+
+fn entry(row: &Row) -> Result<Entry> {
+  Ok(Entry {
+    word: row.get(0)?,
+    definition: row.get(1)?,
+    front_hooks: hooks_from_str(&row.get::<_, String>(2)?),
+    back_hooks: hooks_from_str(&row.get::<_, String>(3)?),
+    playability: row.get(4)?,
+    probability: row.get(5)?,
+  })
+}
+
+/// Adds any columns missing from a table made by an older version.
+fn migrate(conn: &Connection) -> Result<()> {
+  let mut query = conn.prepare("SELECT name FROM pragma_table_info('words')")?;
+  let columns: Vec<String> = query
+    .query_map([], |row| row.get(0))?
+    .collect::<Result<_>>()?;
+  for (column, sql) in MIGRATIONS {
+    if !columns.iter().any(|name| name == column) {
+      conn.execute_batch(sql)?;
+    }
+  }
+  Ok(())
+}
+
+impl SqliteLexicon {
+  /// Opens the database at a path, creating it and its table if needed.
+  pub fn open(path: &Path) -> Result<Self> {
+    Self::with_connection(Connection::open(path)?)
+  }
+
+  /// A database that only lasts as long as the lexicon does.
+  pub fn open_in_memory() -> Result<Self> {
+    Self::with_connection(Connection::open_in_memory()?)
+  }
+
+  fn with_connection(conn: Connection) -> Result<Self> {
+    conn.execute_batch(SCHEMA)?;
+    migrate(&conn)?;
+    Ok(Self { conn })
+  }
+
+  /// The number of words in the database.
+  pub fn len(&self) -> Result<usize> {
+    self
+      .conn
+      .query_row("SELECT COUNT(*) FROM words", [], |row| row.get(0))
+  }
+
+  pub fn is_empty(&self) -> Result<bool> {
+    self.len().map(|len| len == 0)
+  }
+
+  /// Whether a word is in the database.
+  pub fn has(&self, word: &str) -> Result<bool> {
+    let mut query = self
+      .conn
+      .prepare_cached("SELECT 1 FROM words WHERE word = ?1")?;
+    query.exists([word])
+  }
+
+  /// Everything the database holds on a word.
+  pub fn entry(&self, word: &str) -> Result<Option<Entry>> {
+    let mut query = self.conn.prepare_cached(
+      "SELECT word, definition, front_hooks, back_hooks, playability, probability
+       FROM words WHERE word = ?1",
+    )?;
+    query.query_row([word], entry).optional()
+  }
+
+  /// Adds an entry, replacing anything already held on its word.
+  pub fn insert(&mut self, entry: &Entry) -> Result<()> {
+    let mut query = self.conn.prepare_cached(
+      "INSERT OR REPLACE INTO words
+       (word, definition, front_hooks, back_hooks, playability, probability)
+       VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+    )?;
+    query.execute(params![
+      entry.word,
+      entry.definition,
+      hooks_to_string(&entry.front_hooks),
+      hooks_to_string(&entry.back_hooks),
+      entry.playability,
+      entry.probability,
+    ])?;
+    Ok(())
+  }
+
+  /// Adds every word in a trie along with its hooks, in one transaction.
+  ///
+  /// Words already in the database keep their other details, only their
+  /// hooks are updated. Returns the number of words imported.
+  pub fn import(&mut self, trie: &Trie) -> Result<usize> {
+    let tx = self.conn.transaction()?;
+    let mut count = 0;
+    {
+      let mut query = tx.prepare_cached(
+        "INSERT INTO words (word, front_hooks, back_hooks) VALUES (?1, ?2, ?3)
+         ON CONFLICT (word) DO UPDATE SET
+         front_hooks = excluded.front_hooks, back_hooks = excluded.back_hooks",
+      )?;
+      for annotated in trie.annotated_words() {
+        query.execute(params![
+          annotated.word,
+          hooks_to_string(&annotated.front),
+          hooks_to_string(&annotated.back),
+        ])?;
+        count += 1;
+      }
+    }
+    tx.commit()?;
+    Ok(count)
+  }
+
+  /// All the words in the database as a trie.
+  ///
+  /// Words with characters outside of `a-z` can't be held by a trie, so
+  /// are skipped.
+  pub fn export(&self) -> Result<Trie> {
+    let mut query = self.conn.prepare("SELECT word FROM words")?;
+    let words = query.query_map([], |row| row.get::<_, String>(0))?;
+    let mut trie = Trie::empty();
+    for word in words {
+      let word = word?;
+      if word.chars().all(|c| c.is_ascii_lowercase()) {
+        trie += word.as_str();
+      }
+    }
+    Ok(trie)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn round_trip() {
+    // importing should record hooks and exporting should give the words back
+    let trie: Trie = ["at", "cat", "ate", "cats"].into_iter().collect();
+    let mut lexicon = SqliteLexicon::open_in_memory().unwrap();
+    assert_eq!(lexicon.import(&trie).unwrap(), 4);
+    assert_eq!(lexicon.len().unwrap(), 4);
+    assert!(lexicon.has("cat").unwrap() && !lexicon.has("cot").unwrap());
+
+    let at = lexicon.entry("at").unwrap().unwrap();
+    assert_eq!(hooks_to_string(&at.front_hooks), "c");
+    assert_eq!(hooks_to_string(&at.back_hooks), "e");
+    assert_eq!(lexicon.export().unwrap(), trie);
+  }
+
+  #[test]
+  fn keeps_details() {
+    // reimporting shouldn't lose definitions that were added by hand
+    let mut lexicon = SqliteLexicon::open_in_memory().unwrap();
+    let cat = Entry {
+      definition: Some("a small feline".into()),
+      playability: Some(0.5),
+      ..Entry::new("cat")
+    };
+    lexicon.insert(&cat).unwrap();
+    lexicon
+      .import(&["cat", "cats"].into_iter().collect())
+      .unwrap();
+
+    let entry = lexicon.entry("cat").unwrap().unwrap();
+    assert_eq!(entry.definition, cat.definition);
+    assert_eq!(entry.playability, Some(0.5));
+    assert_eq!(hooks_to_string(&entry.back_hooks), "s");
+    assert_eq!(lexicon.entry("dog").unwrap(), None);
+  }
+
+  #[test]
+  fn migrate() {
+    // tables from before the probability column should gain it on opening
+    let conn = Connection::open_in_memory().unwrap();
+    conn
+      .execute_batch(
+        "CREATE TABLE words (
+          word TEXT PRIMARY KEY NOT NULL,
+          definition TEXT,
+          front_hooks TEXT NOT NULL DEFAULT '',
+          back_hooks TEXT NOT NULL DEFAULT '',
+          playability REAL
+        );
+        INSERT INTO words (word, playability) VALUES ('cat', 0.5);",
+      )
+      .unwrap();
+    let mut lexicon = SqliteLexicon::with_connection(conn).unwrap();
+    let cat = lexicon.entry("cat").unwrap().unwrap();
+    assert_eq!((cat.playability, cat.probability), (Some(0.5), None));
+    let dog = Entry {
+      probability: Some(0.25),
+      ..Entry::new("dog")
+    };
+    lexicon.insert(&dog).unwrap();
+    assert_eq!(lexicon.entry("dog").unwrap(), Some(dog));
+  }
+}
@@ -1,7 +1,58 @@
+/*!
+Word lists, tries and the queries a Scrabble player or tool needs on them.
+
+The main entry points are:
+- [`trie_ptr::Trie`], the copy-on-write trie most queries are written for,
+  which loads and saves `.tre` and `.txt` word lists.
+- [`trie::Trie`], a wrapper giving any [`trie::TrieNode`] backend the same
+  set operators and file formats.
+- [`dawg::DawgRoot`], a directed acyclic word graph sharing common suffixes.
+- [`word::Word`] and [`letter::Letter`], the patterns used to query these,
+  such as `c[ao]t` or `..e`.
+- [`lexicon`], for dictionaries shared by long-running tools.
+
+The [`prelude`] imports the common traits and types in one line.
+
+There is no board or game model yet, so move generation is out of scope.
+*/
+
+pub mod alphabet;
+pub mod bag;
+pub mod bench;
+pub mod crossword;
+#[cfg(feature = "dawg")]
 pub mod dawg;
+pub mod formats;
 pub mod letter;
+pub mod lexicon;
+pub mod limits;
+pub mod memory;
+pub mod mph;
+pub mod prelude;
+pub mod queries;
+pub mod rack;
+#[cfg(feature = "serve")]
+pub mod serve;
 pub mod set;
 pub mod set_traits;
+pub mod stats;
+pub mod succinct;
+pub mod tile;
 pub mod trie;
+pub mod trie_map;
 pub mod trie_ptr;
 pub mod word;
+pub mod wordle;
+
+/// A path in the temp directory that's unique to this process, so that
+/// concurrent test runs don't write over each other's files.
+#[cfg(test)]
+pub(crate) fn temp_path(name: impl std::fmt::Display) -> std::path::PathBuf {
+  std::env::temp_dir().join(format!("{}_{name}", std::process::id()))
+}
+
+/// A pointer trie holding exactly the given words, for test fixtures.
+#[cfg(test)]
+pub(crate) fn trie_of(words: &[&str]) -> trie_ptr::Trie {
+  words.iter().copied().collect()
+}
@@ -0,0 +1,62 @@
+/*!
+Bounds on the work done by operations that can grow without limit.
+
+Patterns like `Trie::all(30)` are cheap to build, as every branch shares
+the same nodes, but listing their words or combining them with another
+trie can take more memory or stack than any machine has. The `_within`
+variants of those operations check a [`Limits`] as they go, and stop with
+a [`LimitExceeded`] error instead.
+*/
+
+use std::{error::Error, fmt::Display};
+
+/// How far an operation may go before giving up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+  /// The longest word, and so the deepest recursion, allowed.
+  pub max_depth: usize,
+  /// The most nodes an operation may visit or create.
+  pub max_nodes: usize,
+  /// The most words an operation may produce.
+  pub max_results: usize,
+}
+
+impl Limits {
+  /// Limits that never stop an operation.
+  pub const UNLIMITED: Limits = Limits {
+    max_depth: usize::MAX,
+    max_nodes: usize::MAX,
+    max_results: usize::MAX,
+  };
+}
+
+impl Default for Limits {
+  /// Limits well beyond any real dictionary, but within a typical stack.
+  fn default() -> Self {
+    Limits {
+      max_depth: 64,
+      max_nodes: 50_000_000,
+      max_results: 10_000_000,
+    }
+  }
+}
+
+/// The limit that an operation would have gone past, holding its value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitExceeded {
+  Depth(usize),
+  Nodes(usize),
+  Results(usize),
+}
+
+impl Display for LimitExceeded {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      LimitExceeded::Depth(max) => write!(f, "words longer than the limit of {max} letters"),
+      LimitExceeded::Nodes(max) => write!(f, "more than the limit of {max} nodes"),
+      LimitExceeded::Results(max) => write!(f, "more than the limit of {max} words"),
+    }
+  }
+}
+
+impl Error for LimitExceeded {}
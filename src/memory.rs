@@ -0,0 +1,102 @@
+/*!
+Estimates of how much memory a dictionary is using.
+
+Each backend builds a [`MemoryReport`] by walking its own nodes, so reports
+from different backends can be compared for the same word list.
+*/
+
+use std::fmt::Display;
+
+/// A summary of the nodes making up a dictionary and their size.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoryReport {
+  /// The number of distinct nodes.
+  pub nodes: usize,
+  /// The number of nodes that would be needed if no nodes were shared.
+  pub tree_nodes: usize,
+  /// An estimate of the bytes allocated on the heap for all nodes.
+  pub heap_bytes: usize,
+  /// The number of distinct nodes first reached at each depth.
+  pub depths: Vec<usize>,
+}
+
+impl MemoryReport {
+  /// How many tree nodes each distinct node stands in for on average.
+  ///
+  /// This is always `1.0` for a plain trie and higher for a minimised DAWG.
+  pub fn sharing_factor(&self) -> f64 {
+    self.tree_nodes as f64 / self.nodes.max(1) as f64
+  }
+
+  /// The length of the longest path through the dictionary.
+  pub fn max_depth(&self) -> usize {
+    self.depths.len().saturating_sub(1)
+  }
+}
+
+/// Formats a number of bytes using binary prefixes.
+fn format_bytes(bytes: usize) -> String {
+  let units = ["B", "KiB", "MiB", "GiB"];
+  let mut size = bytes as f64;
+  let mut unit = 0;
+  while size >= 1024.0 && unit + 1 < units.len() {
+    size /= 1024.0;
+    unit += 1;
+  }
+  if unit == 0 {
+    format!("{bytes} {}", units[0])
+  } else {
+    format!("{size:.1} {}", units[unit])
+  }
+}
+
+impl Display for MemoryReport {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    writeln!(
+      f,
+      "{} nodes ({} without sharing, {:.2}x shared)",
+      self.nodes,
+      self.tree_nodes,
+      self.sharing_factor()
+    )?;
+    writeln!(f, "{} on the heap", format_bytes(self.heap_bytes))?;
+    write!(f, "nodes by depth:")?;
+    for (depth, count) in self.depths.iter().enumerate() {
+      write!(f, "\n{depth:>4}: {count}")?;
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::trie_ptr::Trie;
+
+  #[cfg(feature = "parse")]
+  #[test]
+  fn trie() {
+    // every branch of a group shares the same suffix
+    let report = Trie::str("[ab]c").memory_report();
+    assert_eq!(report.nodes, 3);
+    assert_eq!(report.tree_nodes, 5);
+    assert_eq!(report.depths, vec![1, 1, 1]);
+  }
+
+  #[test]
+  fn unshared() {
+    // separately added words don't share any nodes
+    let mut trie = Trie::str("ac");
+    trie += "bc";
+    let report = trie.memory_report();
+    assert_eq!(report.nodes, 5);
+    assert_eq!(report.depths, vec![1, 2, 2]);
+    assert_eq!(report.sharing_factor(), 1.0);
+  }
+
+  #[test]
+  fn bytes() {
+    // sizes should be displayed with a sensible unit
+    assert_eq!(super::format_bytes(512), "512 B");
+    assert_eq!(super::format_bytes(1536), "1.5 KiB");
+  }
+}
@@ -0,0 +1,168 @@
+/*!
+A minimal perfect hash over the words of a dictionary.
+
+Every word is mapped to its alphabetical rank, which is found by walking
+the word and adding up the number of words in each branch that sorts
+before it. Each node stores how many words it leads to, and nodes shared
+between branches (as in a DAWG) are only stored once, so the table stays
+about as small as the dictionary itself.
+*/
+
+use crate::trie::TrieNode;
+use std::{collections::HashMap, ops::Range};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Node {
+  end: bool,
+  /// The number of words in this node's branch, including itself.
+  count: usize,
+  edges: Range<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mph {
+  /// Every distinct node, with the root first.
+  nodes: Vec<Node>,
+  /// The letter and node each edge leads to, in alphabetical order.
+  edges: Vec<(u8, usize)>,
+}
+
+impl Mph {
+  /// Ranks the words of any trie, storing shared nodes once.
+  pub fn new<N: TrieNode>(root: &N) -> Self {
+    let mut mph = Mph {
+      nodes: vec![],
+      edges: vec![],
+    };
+    mph.add(root, &mut HashMap::new());
+    // nodes are added after their children, so the root comes last
+    mph.nodes.reverse();
+    let last = mph.nodes.len() - 1;
+    for (_, node) in &mut mph.edges {
+      *node = last - *node;
+    }
+    mph
+  }
+
+  fn add<N: TrieNode>(&mut self, node: &N, seen: &mut HashMap<*const N, usize>) -> usize {
+    if let Some(&i) = seen.get(&(node as *const N)) {
+      return i;
+    }
+
+    let children: Vec<_> = ('a'..='z')
+      .filter_map(|c| Some((c as u8, self.add(node.get_child(c)?, seen))))
+      .collect();
+    let count = children.iter().map(|&(_, i)| self.nodes[i].count);
+    let count = usize::from(node.is_end()) + count.sum::<usize>();
+
+    let start = self.edges.len();
+    self.edges.extend(children);
+    self.nodes.push(Node {
+      end: node.is_end(),
+      count,
+      edges: start..self.edges.len(),
+    });
+    seen.insert(node, self.nodes.len() - 1);
+    self.nodes.len() - 1
+  }
+
+  /// The number of words hashed.
+  pub fn len(&self) -> usize {
+    self.nodes[0].count
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// The index of a word, which is unique and less than [`Mph::len`].
+  pub fn index(&self, word: &str) -> Option<usize> {
+    let mut node = &self.nodes[0];
+    let mut index = 0;
+    for c in word.bytes() {
+      index += usize::from(node.end);
+      let mut edges = self.edges[node.edges.clone()].iter();
+      let next = edges.find(|&&(label, child)| {
+        if label != c {
+          index += self.nodes[child].count
+        }
+        label >= c
+      });
+      node = match next {
+        Some(&(label, child)) if label == c => &self.nodes[child],
+        _ => return None,
+      };
+    }
+    node.end.then_some(index)
+  }
+
+  /// The word with an index, if it is less than [`Mph::len`].
+  pub fn word(&self, mut index: usize) -> Option<String> {
+    let mut node = &self.nodes[0];
+    let mut word = String::new();
+    while !(node.end && index == 0) {
+      index -= usize::from(node.end);
+      let edges = &self.edges[node.edges.clone()];
+      let &(label, child) = edges.iter().find(|&&(_, child)| {
+        let count = self.nodes[child].count;
+        let found = index < count;
+        if !found {
+          index -= count
+        }
+        found
+      })?;
+      word.push(label as char);
+      node = &self.nodes[child];
+    }
+    Some(word)
+  }
+
+  /// The bytes allocated for the table.
+  pub fn heap_bytes(&self) -> usize {
+    self.nodes.capacity() * std::mem::size_of::<Node>()
+      + self.edges.capacity() * std::mem::size_of::<(u8, usize)>()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::trie_ptr::Trie;
+
+  fn words() -> Vec<String> {
+    [
+      "a", "an", "and", "ant", "be", "bee", "cat", "cats", "hat", "hats",
+    ]
+    .map(String::from)
+    .to_vec()
+  }
+
+  #[test]
+  fn round_trip() {
+    // words should be numbered alphabetically, and back again
+    let mph = Mph::new(&words().into_iter().collect::<Trie>());
+    assert_eq!(mph.len(), 10);
+    for (i, word) in words().iter().enumerate() {
+      assert_eq!(mph.index(word), Some(i), "{word}");
+      assert_eq!(mph.word(i).as_ref(), Some(word));
+    }
+    assert_eq!(mph.word(10), None);
+  }
+
+  #[test]
+  fn missing() {
+    // only stored words should have an index
+    let mph = Mph::new(&words().into_iter().collect::<Trie>());
+    for word in ["", "b", "ants", "ca", "dog", "A"] {
+      assert_eq!(mph.index(word), None, "{word}");
+    }
+  }
+
+  #[test]
+  fn empty() {
+    // the empty trie should hash nothing
+    let mph = Mph::new(&Trie::empty());
+    assert!(mph.is_empty());
+    assert_eq!(mph.word(0), None);
+  }
+}
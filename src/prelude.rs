@@ -0,0 +1,27 @@
+/*!
+The traits and types most uses of the crate need, so that calling trait
+methods such as [`TrieNode::strings`] only takes a single import:
+
+```
+use scrabble::prelude::*;
+
+let trie: Trie = ["cat", "cot"].into_iter().collect();
+assert_eq!(trie.strings().collect::<Vec<_>>(), ["cat", "cot"]);
+```
+
+The generic [`crate::trie::Trie`] wrapper shares its name with the pointer
+trie exported here, so it has to be imported from its own module.
+*/
+
+#[cfg(feature = "dawg")]
+#[doc(no_inline)]
+pub use crate::dawg::DawgRoot;
+#[doc(no_inline)]
+pub use crate::{
+  letter::Letter,
+  set::Set,
+  set_traits::StringSet,
+  trie::TrieNode,
+  trie_ptr::Trie,
+  word::{ParseError, Word},
+};
@@ -0,0 +1,91 @@
+/*!
+Canned lexicon queries that Scrabble players ask for all the time.
+
+Each query works on any [`TrieNode`], yielding matching words in
+alphabetical order. Only `aeiou` are counted as vowels, so `y` is a
+consonant throughout.
+*/
+
+use crate::trie::TrieNode;
+
+/// The share of a word's letters that make it a vowel or consonant dump.
+pub const DUMP_RATIO: f64 = 0.75;
+
+pub(crate) fn is_vowel(c: char) -> bool {
+  matches!(c, 'a' | 'e' | 'i' | 'o' | 'u')
+}
+
+fn vowel_ratio(word: &str) -> f64 {
+  word.chars().filter(|&c| is_vowel(c)).count() as f64 / word.len() as f64
+}
+
+/// Words with a `q` that isn't followed by a `u`.
+pub fn q_without_u<N: TrieNode>(trie: &N) -> impl Iterator<Item = String> + '_ {
+  trie.strings().filter(|word| {
+    let mut chars = word.chars().peekable();
+    let mut found = false;
+    while let Some(c) = chars.next() {
+      if c == 'q' && chars.peek() != Some(&'u') {
+        found = true
+      }
+    }
+    found
+  })
+}
+
+/// Words where at least [`DUMP_RATIO`] of the letters are vowels.
+pub fn vowel_dumps<N: TrieNode>(trie: &N) -> impl Iterator<Item = String> + '_ {
+  trie
+    .strings()
+    .filter(|word| vowel_ratio(word) >= DUMP_RATIO)
+}
+
+/// Words where at least [`DUMP_RATIO`] of the letters are consonants.
+pub fn consonant_dumps<N: TrieNode>(trie: &N) -> impl Iterator<Item = String> + '_ {
+  trie
+    .strings()
+    .filter(|word| 1.0 - vowel_ratio(word) >= DUMP_RATIO)
+}
+
+/// Words with any of the high scoring `j`, `q`, `x` or `z`.
+pub fn jqxz<N: TrieNode>(trie: &N) -> impl Iterator<Item = String> + '_ {
+  trie
+    .strings()
+    .filter(|word| word.contains(['j', 'q', 'x', 'z']))
+}
+
+/// Words without any vowels.
+pub fn no_vowels<N: TrieNode>(trie: &N) -> impl Iterator<Item = String> + '_ {
+  trie.strings().filter(|word| !word.contains(is_vowel))
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::trie_of;
+  use crate::trie_ptr::Trie;
+
+  fn words() -> Trie {
+    trie_of(&["aa", "aeon", "cwm", "faqir", "qi", "quiz", "rhythm", "jab", "eau"])
+  }
+
+  #[test]
+  fn queries() {
+    // each query should find exactly its kind of word
+    let trie = words();
+    assert_eq!(q_without_u(&trie).collect::<Vec<_>>(), ["faqir", "qi"]);
+    assert_eq!(
+      vowel_dumps(&trie).collect::<Vec<_>>(),
+      ["aa", "aeon", "eau"]
+    );
+    assert_eq!(
+      consonant_dumps(&trie).collect::<Vec<_>>(),
+      ["cwm", "rhythm"]
+    );
+    assert_eq!(
+      jqxz(&trie).collect::<Vec<_>>(),
+      ["faqir", "jab", "qi", "quiz"]
+    );
+    assert_eq!(no_vowels(&trie).collect::<Vec<_>>(), ["cwm", "rhythm"]);
+  }
+}
@@ -0,0 +1,136 @@
+/*!
+The tiles a player holds, written as letters with `?` for a blank.
+
+Only `aeiou` are counted as vowels, as in [`crate::queries`].
+*/
+
+use crate::{
+  letter::LetterCounts,
+  queries::is_vowel,
+  tile::{self, InvalidTile, Tile, BLANK},
+};
+use std::{fmt::Display, str::FromStr};
+
+/// The tiles on a rack, in the order the player has them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Rack(Vec<Tile>);
+
+impl FromStr for Rack {
+  type Err = InvalidTile;
+  /// Reads tiles ignoring case, so `AEIN?RT` and `aein?rt` are the same.
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let tiles = s.chars().map(|c| match c.to_ascii_lowercase() {
+      c @ 'a'..='z' => Ok(Tile::Letter(c)),
+      BLANK => Ok(Tile::Blank(None)),
+      _ => Err(InvalidTile(c)),
+    });
+    tiles.collect::<Result<_, _>>().map(Rack)
+  }
+}
+
+impl Display for Rack {
+  /// Writes natural tiles in lowercase and blanks as `?`, dropping any
+  /// letter a blank was designated as.
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let tiles: String = self
+      .0
+      .iter()
+      .map(|tile| match tile {
+        Tile::Letter(c) => *c,
+        Tile::Blank(_) => BLANK,
+      })
+      .collect();
+    f.pad(&tiles)
+  }
+}
+
+impl Rack {
+  pub fn tiles(&self) -> &[Tile] {
+    &self.0
+  }
+
+  pub fn len(&self) -> usize {
+    self.0.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+
+  pub fn blanks(&self) -> usize {
+    self.0.iter().filter(|tile| tile.is_blank()).count()
+  }
+
+  /// The face value of the tiles, where blanks score 0.
+  pub fn score(&self) -> u32 {
+    tile::score(&self.0)
+  }
+
+  /// The natural letters on the rack, in order.
+  fn letters(&self) -> impl Iterator<Item = char> + '_ {
+    self.0.iter().filter_map(|tile| match tile {
+      Tile::Letter(c) => Some(*c),
+      Tile::Blank(_) => None,
+    })
+  }
+
+  /// How many of each letter are on the rack, not counting blanks.
+  pub fn counts(&self) -> LetterCounts {
+    LetterCounts::from_text(&self.letters().collect::<String>())
+  }
+
+  /// The tiles in alphabetical order with any blanks last, which is the
+  /// same for every ordering of a rack, i.e. `aeinrst?` for `?retains`.
+  pub fn alphagram(&self) -> String {
+    let blanks = std::iter::repeat_n(BLANK, self.blanks());
+    self.counts().chars().chain(blanks).collect()
+  }
+
+  /// Shuffles the tiles into a random order.
+  #[cfg(feature = "rand")]
+  pub fn shuffle<R: rand::Rng + ?Sized>(&mut self, rng: &mut R) {
+    rand::seq::SliceRandom::shuffle(self.0.as_mut_slice(), rng)
+  }
+
+  /// Splits the letters into vowels and consonants, keeping their order
+  /// and leaving out any blanks.
+  pub fn vowel_consonant_split(&self) -> (Rack, Rack) {
+    let (vowels, consonants) = self.letters().partition(|&c| is_vowel(c));
+    let rack = |letters: Vec<char>| Rack(letters.into_iter().map(Tile::Letter).collect());
+    (rack(vowels), rack(consonants))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn alphagram() {
+    // every ordering of a rack should share an alphagram
+    let rack: Rack = "?RETAINS".parse().unwrap();
+    assert_eq!(rack.to_string(), "?retains");
+    assert_eq!(rack.alphagram(), "aeinrst?");
+    assert_eq!(rack.blanks(), 1);
+    assert_eq!(rack.score(), 7);
+    assert_eq!("rack!".parse::<Rack>(), Err(InvalidTile('!')));
+  }
+
+  #[test]
+  #[cfg(feature = "rand")]
+  fn shuffle() {
+    // shuffling should only change the order of the tiles
+    let mut rack: Rack = "qwertyu".parse().unwrap();
+    rack.shuffle(&mut rand::thread_rng());
+    assert_eq!(rack.alphagram(), "eqrtuwy");
+  }
+
+  #[test]
+  fn split() {
+    // y is a consonant and blanks are in neither half
+    let rack: Rack = "yeah?io".parse().unwrap();
+    let (vowels, consonants) = rack.vowel_consonant_split();
+    assert_eq!(vowels.to_string(), "eaio");
+    assert_eq!(consonants.to_string(), "yh");
+  }
+}
@@ -0,0 +1,186 @@
+/*!
+A minimal HTTP service for querying a dictionary.
+
+Routes:
+- `/check/:word`: `true` or `false` for whether the word is in the dictionary
+- `/anagrams/:letters`: every word using all of the letters, `?` for blanks
+- `/pattern/:pattern`: every word matching a [`Word`] pattern
+
+Responses are plain text with one result per line. The dictionary is held
+in a [`SharedLexicon`], so it can be reloaded without restarting the server,
+and each request queries a [`FrozenTrie`] taken from it.
+
+Racks longer than [`MAX_RACK`] and pattern searches beyond [`LIMITS`] are rejected
+with a 400, and searches run on tokio's blocking threads so a slow one
+can't stall the others.
+*/
+
+use crate::{
+  lexicon::{FrozenTrie, SharedLexicon},
+  limits::{LimitExceeded, Limits},
+  trie_ptr::Trie,
+  word::Word,
+};
+use axum::{
+  extract::{Path, State},
+  http::StatusCode,
+  routing::get,
+  Router,
+};
+use std::{io, net::SocketAddr};
+
+/// The most tiles in a rack, enough for a word across the whole board.
+pub const MAX_RACK: usize = 15;
+
+/// The limits on a pattern search, whose words can't be longer than the
+/// board either.
+pub const LIMITS: Limits = Limits {
+  max_depth: 15,
+  max_nodes: 1_000_000,
+  max_results: 1_000_000,
+};
+
+type Response = Result<String, (StatusCode, String)>;
+
+fn bad_request(message: String) -> (StatusCode, String) {
+  (StatusCode::BAD_REQUEST, message)
+}
+
+/// Runs a search on the current dictionary away from the async threads.
+async fn search<F>(lexicon: SharedLexicon, f: F) -> Response
+where
+  F: FnOnce(&FrozenTrie) -> Response + Send + 'static,
+{
+  let trie = lexicon.frozen();
+  tokio::task::spawn_blocking(move || f(&trie))
+    .await
+    .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?
+}
+
+fn check_in(trie: &FrozenTrie, word: &str) -> Response {
+  if !word.chars().all(|c| c.is_ascii_lowercase()) {
+    return Err(bad_request(format!("`{word}` is not a word in a-z")));
+  }
+  Ok(trie.has(word).to_string())
+}
+
+fn anagrams_in(trie: &FrozenTrie, letters: &str) -> Response {
+  if letters.chars().count() > MAX_RACK {
+    return Err(bad_request(format!(
+      "racks can't have more than {MAX_RACK} tiles"
+    )));
+  }
+  let words = trie.anagrams(letters);
+  let words = words.ok_or_else(|| bad_request(format!("`{letters}` is not a rack")))?;
+  Ok(words.join("\n"))
+}
+
+fn pattern_in(trie: &FrozenTrie, pattern: &str, limits: &Limits) -> Response {
+  let word: Word = pattern
+    .parse()
+    .map_err(|_| bad_request(format!("`{pattern}` is not a pattern")))?;
+  let too_many = |err| bad_request(format!("`{pattern}` matches {err}"));
+  if word.len() > limits.max_depth {
+    return Err(too_many(LimitExceeded::Depth(limits.max_depth)));
+  }
+  // the pattern shares its branches so is cheap to build, the work is in
+  // walking the dictionary alongside it
+  let matches = trie.and_within(&Trie::word(word), limits).map_err(too_many)?;
+  Ok(matches.strings_within(limits).map_err(too_many)?.join("\n"))
+}
+
+async fn check(State(lexicon): State<SharedLexicon>, Path(word): Path<String>) -> Response {
+  check_in(&lexicon.frozen(), &word)
+}
+
+async fn anagrams(State(lexicon): State<SharedLexicon>, Path(letters): Path<String>) -> Response {
+  search(lexicon, move |trie| anagrams_in(trie, &letters)).await
+}
+
+async fn pattern(State(lexicon): State<SharedLexicon>, Path(pattern): Path<String>) -> Response {
+  search(lexicon, move |trie| pattern_in(trie, &pattern, &LIMITS)).await
+}
+
+/// The routes for the service, for use in a larger application.
+pub fn router(lexicon: SharedLexicon) -> Router {
+  Router::new()
+    .route("/check/:word", get(check))
+    .route("/anagrams/:letters", get(anagrams))
+    .route("/pattern/:pattern", get(pattern))
+    .with_state(lexicon)
+}
+
+/// Serves the dictionary on an address until the server fails.
+pub async fn serve(lexicon: SharedLexicon, addr: SocketAddr) -> io::Result<()> {
+  let listener = tokio::net::TcpListener::bind(addr).await?;
+  axum::serve(listener, router(lexicon)).await
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use std::future::Future;
+
+  fn block_on<F: Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+      .build()
+      .unwrap()
+      .block_on(future)
+  }
+
+  fn lexicon() -> SharedLexicon {
+    SharedLexicon::new(crate::trie_of(&["act", "cat", "cot", "dog"]))
+  }
+
+  #[test]
+  fn routes() {
+    // the route paths should all be valid
+    let _ = router(lexicon());
+  }
+
+  #[test]
+  fn check_word() {
+    // words should be checked against the dictionary
+    let response = block_on(check(State(lexicon()), Path("cat".into())));
+    assert_eq!(response, Ok("true".into()));
+    let response = block_on(check(State(lexicon()), Path("[ab]".into())));
+    assert_eq!(response.unwrap_err().0, StatusCode::BAD_REQUEST);
+  }
+
+  #[test]
+  fn anagram_letters() {
+    // anagrams should be listed one per line
+    let response = block_on(anagrams(State(lexicon()), Path("tca".into())));
+    assert_eq!(response, Ok("act\ncat".into()));
+    let rack = "?".repeat(MAX_RACK + 1);
+    let response = block_on(anagrams(State(lexicon()), Path(rack)));
+    assert_eq!(response.unwrap_err().0, StatusCode::BAD_REQUEST);
+  }
+
+  #[test]
+  fn pattern_matches() {
+    // every matching word should be listed
+    let response = block_on(pattern(State(lexicon()), Path("c[ao]t".into())));
+    assert_eq!(response, Ok("cat\ncot".into()));
+    // wildcards should only be limited by the words they actually match
+    let response = block_on(pattern(State(lexicon()), Path(".".repeat(5))));
+    assert_eq!(response, Ok("".into()));
+    let response = block_on(pattern(State(lexicon()), Path("...".into())));
+    assert_eq!(response, Ok("act\ncat\ncot\ndog".into()));
+    let response = block_on(pattern(State(lexicon()), Path("c".repeat(16))));
+    assert_eq!(response.unwrap_err().0, StatusCode::BAD_REQUEST);
+    // searches going past the limits should be refused
+    let limits = Limits {
+      max_results: 3,
+      ..LIMITS
+    };
+    let response = pattern_in(&lexicon().frozen(), "...", &limits);
+    assert_eq!(response.unwrap_err().0, StatusCode::BAD_REQUEST);
+    let limits = Limits {
+      max_nodes: 4,
+      ..LIMITS
+    };
+    let response = pattern_in(&lexicon().frozen(), "c.t", &limits);
+    assert_eq!(response.unwrap_err().0, StatusCode::BAD_REQUEST);
+  }
+}
@@ -0,0 +1,151 @@
+/*!
+Probabilities of drawing words from a bag of tiles.
+
+A word's playability is the chance that a freshly drawn rack holds all of
+its letters, with blanks standing in for any that are missing. Study lists
+are usually ordered by this, most probable first.
+*/
+
+use crate::{alphabet::into_index, bag::Bag, trie_ptr::Trie};
+
+/// The number of tiles in a full rack.
+pub const RACK_SIZE: usize = 7;
+
+/// The number of ways to choose `k` of `n` things.
+fn choose(n: usize, k: usize) -> f64 {
+  if k > n {
+    return 0.0;
+  }
+  (0..k.min(n - k)).fold(1.0, |ways, i| ways * (n - i) as f64 / (i + 1) as f64)
+}
+
+/// The chance that a full rack can play a word, see [`playability_in`].
+pub fn playability(word: &str, bag: &Bag) -> f64 {
+  playability_in(word, bag, RACK_SIZE)
+}
+
+/// The chance that a rack of `rack` tiles drawn from the bag holds the
+/// letters of a word, using blanks for any letters it is short of.
+///
+/// Words with letters outside of `a-z` are never playable.
+pub fn playability_in(word: &str, bag: &Bag, rack: usize) -> f64 {
+  let mut needed = [0usize; 26];
+  for c in word.chars() {
+    match into_index(c) {
+      Some(i) => needed[i] += 1,
+      None => return 0.0,
+    }
+  }
+  let rack = rack.min(bag.len());
+  if word.len() > rack {
+    return 0.0;
+  }
+
+  // ways[t][s] counts draws of t letter tiles that are s tiles short of the
+  // word, where anything over the rack size is as bad as the rack size
+  let mut ways = vec![vec![0.0; rack + 1]; rack + 1];
+  ways[0][0] = 1.0;
+  for (&count, &need) in bag.counts().iter().zip(&needed) {
+    let mut next = vec![vec![0.0; rack + 1]; rack + 1];
+    for (t, row) in ways.iter().enumerate() {
+      for (s, &w) in row.iter().enumerate().filter(|(_, &w)| w != 0.0) {
+        for k in 0..=count.min(rack - t) {
+          let short = (s + need.saturating_sub(k)).min(rack);
+          next[t + k][short] += w * choose(count, k);
+        }
+      }
+    }
+    ways = next;
+  }
+
+  // the rest of the rack is blanks, which must cover the shortfall
+  let mut playable = 0.0;
+  for (t, row) in ways.iter().enumerate() {
+    let blanks = rack - t;
+    let covered: f64 = row[..=blanks].iter().sum();
+    playable += covered * choose(bag.blanks(), blanks);
+  }
+  playable / choose(bag.len(), rack)
+}
+
+/// The `k` seven letter words that are most likely to be drawn, along with
+/// their playability, most probable first.
+pub fn most_probable_bingos(trie: &Trie, bag: &Bag, k: usize) -> Vec<(String, f64)> {
+  let mut bingos: Vec<_> = trie
+    .words_of_length(RACK_SIZE)
+    .map(|word| {
+      let p = playability(&word, bag);
+      (word, p)
+    })
+    .collect();
+  bingos.sort_by(|(w0, p0), (w1, p1)| p1.total_cmp(p0).then_with(|| w0.cmp(w1)));
+  bingos.truncate(k);
+  bingos
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::trie_of;
+
+  fn small_bag() -> Bag {
+    let mut counts = [0; 26];
+    counts[..3].copy_from_slice(&[2, 2, 1]);
+    Bag::new(counts, 1)
+  }
+
+  /// Counts the racks that can play a word by trying every draw.
+  fn brute_force(word: &str, tiles: &[char], rack: usize) -> f64 {
+    let n = tiles.len();
+    let (mut playable, mut total) = (0, 0);
+    for mask in 0u32..1 << n {
+      if mask.count_ones() as usize != rack {
+        continue;
+      }
+      total += 1;
+      let mut drawn: Vec<_> = (0..n)
+        .filter(|i| mask >> i & 1 == 1)
+        .map(|i| tiles[i])
+        .collect();
+      let short = word
+        .chars()
+        .filter(|c| match drawn.iter().position(|d| d == c) {
+          Some(i) => {
+            drawn.swap_remove(i);
+            false
+          }
+          None => true,
+        });
+      if short.count() <= drawn.iter().filter(|&&d| d == '?').count() {
+        playable += 1
+      }
+    }
+    playable as f64 / total as f64
+  }
+
+  #[test]
+  fn matches_brute_force() {
+    // the counted probability should match trying every draw
+    let tiles = ['a', 'a', 'b', 'b', 'c', '?'];
+    for word in ["a", "ab", "cc", "abc", "aab", "d"] {
+      for rack in 1..=4 {
+        let expected = brute_force(word, &tiles, rack);
+        let p = playability_in(word, &small_bag(), rack);
+        assert!(
+          (p - expected).abs() < 1e-9,
+          "{word} in {rack}: {p} != {expected}"
+        );
+      }
+    }
+  }
+
+  #[test]
+  fn bingos() {
+    // more common letters should make for more probable bingos
+    let trie = trie_of(&["aeinrst", "jukebox", "retains"]);
+    let bingos = most_probable_bingos(&trie, &Bag::english(), 2);
+    let words: Vec<_> = bingos.iter().map(|(word, _)| word.as_str()).collect();
+    assert_eq!(words, ["aeinrst", "retains"]);
+    assert!(bingos[0].1 > 0.0 && bingos[0].1 < 1.0);
+  }
+}
@@ -0,0 +1,134 @@
+/// An append-only bit vector supporting rank and select queries.
+///
+/// Ranks are cached for each 64 bit word once building is finished, so
+/// `rank1` is constant time and `select0` is a binary search.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BitVec {
+  words: Vec<u64>,
+  len: usize,
+  /// The number of ones before each word.
+  ranks: Vec<usize>,
+  ones: usize,
+}
+
+impl BitVec {
+  pub fn push(&mut self, bit: bool) {
+    if self.len.is_multiple_of(64) {
+      self.words.push(0)
+    }
+    if bit {
+      *self.words.last_mut().expect("word was pushed") |= 1 << (self.len % 64)
+    }
+    self.len += 1
+  }
+
+  /// Builds the rank directory, which must be done before querying.
+  pub fn finish(&mut self) {
+    let mut ones = 0;
+    self.ranks = self
+      .words
+      .iter()
+      .map(|word| {
+        let rank = ones;
+        ones += word.count_ones() as usize;
+        rank
+      })
+      .collect();
+    self.ones = ones;
+  }
+
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  pub fn get(&self, i: usize) -> bool {
+    i < self.len && self.words[i / 64] & (1 << (i % 64)) > 0
+  }
+
+  /// The number of ones before position `i`.
+  pub fn rank1(&self, i: usize) -> usize {
+    let (word, bit) = (i / 64, i % 64);
+    match self.ranks.get(word) {
+      Some(rank) => rank + (self.words[word] & ((1 << bit) - 1)).count_ones() as usize,
+      None => self.ones,
+    }
+  }
+
+  /// The position of the `k`th zero, counting from zero.
+  pub fn select0(&self, k: usize) -> Option<usize> {
+    let zeros_before = |word: usize| word * 64 - self.ranks[word];
+    // find the last word with at most k zeros before it
+    let (mut lo, mut hi) = (0, self.ranks.len());
+    while lo < hi {
+      let mid = (lo + hi) / 2;
+      if zeros_before(mid) <= k {
+        lo = mid + 1
+      } else {
+        hi = mid
+      }
+    }
+    let word = lo.checked_sub(1)?;
+
+    let mut left = k - zeros_before(word);
+    for i in word * 64..(word * 64 + 64).min(self.len) {
+      if !self.get(i) {
+        if left == 0 {
+          return Some(i);
+        }
+        left -= 1
+      }
+    }
+    None
+  }
+
+  /// The bytes used by the bits and rank directory.
+  pub fn heap_bytes(&self) -> usize {
+    self.words.capacity() * 8 + self.ranks.capacity() * std::mem::size_of::<usize>()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn bits(pattern: &str) -> BitVec {
+    let mut bits = BitVec::default();
+    for c in pattern.chars() {
+      bits.push(c == '1')
+    }
+    bits.finish();
+    bits
+  }
+
+  #[test]
+  fn rank() {
+    // ranks should count the ones strictly before a position
+    let bits = bits("1011001");
+    let ranks: Vec<usize> = (0..=7).map(|i| bits.rank1(i)).collect();
+    assert_eq!(ranks, [0, 1, 1, 2, 3, 3, 3, 4]);
+  }
+
+  #[test]
+  fn select() {
+    // select should find each zero in turn
+    let bits = bits("1011001");
+    let zeros: Vec<_> = (0..4).map(|k| bits.select0(k)).collect();
+    assert_eq!(zeros, [Some(1), Some(4), Some(5), None]);
+  }
+
+  #[test]
+  fn across_words() {
+    // queries should work past the first 64 bits
+    let pattern: String = (0..200)
+      .map(|i| if i % 3 == 0 { '0' } else { '1' })
+      .collect();
+    let bits = bits(&pattern);
+    assert_eq!(bits.rank1(200), 133);
+    assert_eq!(bits.select0(50), Some(150));
+    assert_eq!(bits.select0(67), None);
+  }
+}
@@ -0,0 +1,164 @@
+/*!
+A compact, read-only dictionary using a level-order unary degree sequence
+(LOUDS) to store the shape of a trie.
+
+Nodes are numbered in breadth-first order. Each node is written as a `1`
+for every child followed by a `0`, after a `10` for a virtual super root,
+so that the `n`th `1` bit stands for node `n`. The children of node `v`
+are then the `1`s following the `v`th `0`, which [`BitVec::select0`] finds,
+and [`BitVec::rank1`] turns each of those back into a node number.
+
+Each node needs just over two bits of shape, one bit for whether it ends a
+word and one byte for the letter leading to it, rather than a pointer for
+every letter of the alphabet.
+*/
+
+mod bits;
+pub use bits::BitVec;
+
+#[cfg(feature = "dawg")]
+use crate::dawg::{Dawg, DawgRoot};
+use crate::trie::TrieNode;
+use std::collections::VecDeque;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Louds {
+  /// The degree of each node in unary, in breadth-first order.
+  shape: BitVec,
+  /// Whether each node ends a word.
+  ends: BitVec,
+  /// The letter on the edge into each node, apart from the root.
+  labels: Vec<u8>,
+}
+
+impl Louds {
+  /// Encodes any trie, copying nodes that are shared between branches.
+  pub fn new<N: TrieNode>(root: &N) -> Self {
+    Self::encode(root, N::is_end, N::get_child)
+  }
+
+  /// Encodes a dawg, copying its shared suffixes out into a tree.
+  #[cfg(feature = "dawg")]
+  pub fn from_dawg(dawg: &DawgRoot) -> Self {
+    Self::encode(dawg.root(), Dawg::is_end, Dawg::get_child)
+  }
+
+  /// Encodes the nodes reachable from a root in breadth-first order.
+  fn encode<'a, N>(
+    root: &'a N,
+    is_end: impl Fn(&'a N) -> bool,
+    get_child: impl Fn(&'a N, char) -> Option<&'a N>,
+  ) -> Self {
+    let mut louds = Louds::default();
+    louds.shape.push(true);
+    louds.shape.push(false);
+
+    let mut queue = VecDeque::from([root]);
+    while let Some(node) = queue.pop_front() {
+      louds.ends.push(is_end(node));
+      for c in 'a'..='z' {
+        if let Some(child) = get_child(node, c) {
+          louds.shape.push(true);
+          louds.labels.push(c as u8);
+          queue.push_back(child);
+        }
+      }
+      louds.shape.push(false);
+    }
+
+    louds.shape.finish();
+    louds.ends.finish();
+    louds
+  }
+
+  /// The child of a node for a letter, if there is one.
+  fn child(&self, node: usize, c: u8) -> Option<usize> {
+    let start = self.shape.select0(node)? + 1;
+    (start..self.shape.len())
+      .take_while(|&i| self.shape.get(i))
+      .map(|i| self.shape.rank1(i))
+      .find(|&child| self.labels[child - 1] == c)
+  }
+
+  /// Whether the dictionary contains a word, matched exactly.
+  pub fn has(&self, word: &str) -> bool {
+    let node = word.bytes().try_fold(0, |node, c| self.child(node, c));
+    node.is_some_and(|node| self.ends.get(node))
+  }
+
+  /// The number of nodes stored.
+  pub fn nodes(&self) -> usize {
+    self.ends.len()
+  }
+
+  /// The number of words stored.
+  pub fn len(&self) -> usize {
+    self.ends.rank1(self.ends.len())
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// The bytes allocated for the encoding.
+  pub fn heap_bytes(&self) -> usize {
+    self.shape.heap_bytes() + self.ends.heap_bytes() + self.labels.capacity()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::trie_of;
+  use crate::trie_ptr::Trie;
+
+  fn words() -> Trie {
+    trie_of(&["a", "an", "and", "ant", "be", "bee", "cat"])
+  }
+
+  #[test]
+  fn has() {
+    // the encoding should contain exactly the trie's words
+    let louds = Louds::new(&words());
+    for word in words() {
+      assert!(louds.has(&word), "{word}")
+    }
+    for word in ["", "b", "ca", "ants", "dog", "A"] {
+      assert!(!louds.has(word), "{word}")
+    }
+  }
+
+  #[cfg(feature = "dawg")]
+  #[test]
+  fn from_dawg() {
+    // a minimised dawg should encode the same words as its trie
+    let trie = words();
+    let dawg = DawgRoot::from_node(&trie);
+    let louds = Louds::from_dawg(&dawg);
+    for word in words() {
+      assert!(louds.has(&word), "{word}")
+    }
+    for word in ["", "b", "ca", "ants", "dog", "A"] {
+      assert!(!louds.has(word), "{word}")
+    }
+    assert_eq!(louds, Louds::new(&trie));
+  }
+
+  #[test]
+  fn counts() {
+    // there should be one node per trie node and one end per word
+    let trie = words();
+    let louds = Louds::new(&trie);
+    assert_eq!(louds.len(), 7);
+    assert_eq!(louds.nodes(), trie.memory_report().tree_nodes);
+  }
+
+  #[test]
+  fn empty() {
+    // the empty trie should only have a root
+    let louds = Louds::new(&Trie::empty());
+    assert!(louds.is_empty());
+    assert_eq!(louds.nodes(), 1);
+    assert!(!louds.has(""));
+  }
+}
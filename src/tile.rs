@@ -0,0 +1,113 @@
+/*!
+Tiles, which keep track of whether a letter came from a blank.
+
+Tiles are written as in GCG files: natural tiles as uppercase letters,
+blanks designated as a letter as that letter in lowercase, and blanks yet
+to be played as `?`.
+*/
+
+use crate::{alphabet::into_index, bag::ENGLISH_SCORES};
+use std::{error::Error, fmt::Display};
+
+/// The character standing for a blank tile.
+pub const BLANK: char = '?';
+
+/// A single tile, on a rack or played on the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Tile {
+  /// A natural tile for a letter in `a-z`.
+  Letter(char),
+  /// A blank, along with the letter it was designated as once played.
+  Blank(Option<char>),
+}
+
+/// A character that isn't a letter or a blank.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidTile(pub char);
+
+impl Display for InvalidTile {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "`{}` isn't a letter or `{BLANK}`", self.0)
+  }
+}
+
+impl Error for InvalidTile {}
+
+impl Tile {
+  /// The letter the tile stands for, if it has one yet.
+  pub fn letter(&self) -> Option<char> {
+    match *self {
+      Tile::Letter(c) => Some(c),
+      Tile::Blank(c) => c,
+    }
+  }
+
+  pub fn is_blank(&self) -> bool {
+    matches!(self, Tile::Blank(_))
+  }
+
+  /// The tile's face value in the English game, where blanks score 0.
+  pub fn score(&self) -> u32 {
+    match *self {
+      Tile::Letter(c) => into_index(c).map_or(0, |i| ENGLISH_SCORES[i]),
+      Tile::Blank(_) => 0,
+    }
+  }
+
+  /// Reads a tile in GCG notation, see the [module docs](self).
+  pub fn from_gcg(c: char) -> Result<Self, InvalidTile> {
+    match c {
+      'A'..='Z' => Ok(Tile::Letter(c.to_ascii_lowercase())),
+      'a'..='z' => Ok(Tile::Blank(Some(c))),
+      BLANK => Ok(Tile::Blank(None)),
+      _ => Err(InvalidTile(c)),
+    }
+  }
+
+  /// Writes a tile in GCG notation, see the [module docs](self).
+  pub fn to_gcg(&self) -> char {
+    match *self {
+      Tile::Letter(c) => c.to_ascii_uppercase(),
+      Tile::Blank(Some(c)) => c,
+      Tile::Blank(None) => BLANK,
+    }
+  }
+}
+
+impl Display for Tile {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.to_gcg())
+  }
+}
+
+/// The total face value of some tiles, with blanks scoring 0.
+pub fn score(tiles: &[Tile]) -> u32 {
+  tiles.iter().map(Tile::score).sum()
+}
+
+/// Reads a word in GCG notation, i.e. `QuIZ` with a blank as the `u`.
+pub fn from_gcg(word: &str) -> Result<Vec<Tile>, InvalidTile> {
+  word.chars().map(Tile::from_gcg).collect()
+}
+
+/// Writes tiles in GCG notation.
+pub fn to_gcg(tiles: &[Tile]) -> String {
+  tiles.iter().map(Tile::to_gcg).collect()
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn gcg() {
+    // blanks should keep their letter but score nothing
+    let tiles = from_gcg("QuIZ").unwrap();
+    assert_eq!(tiles[1], Tile::Blank(Some('u')));
+    assert_eq!(tiles[0].letter(), Some('q'));
+    assert_eq!(score(&tiles), 10 + 1 + 10);
+    assert_eq!(to_gcg(&tiles), "QuIZ");
+    assert_eq!(from_gcg("A?").unwrap()[1], Tile::Blank(None));
+    assert_eq!(from_gcg("A-"), Err(InvalidTile('-')));
+  }
+}
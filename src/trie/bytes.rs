@@ -0,0 +1,365 @@
+/*!
+A trie over arbitrary bytes, for keys that aren't limited to `a-z`.
+
+Nodes keep their children in a sorted list rather than a 256 entry array,
+so sparse tries stay small. Any UTF-8 string can be stored, along with
+keys that aren't strings at all.
+
+Saved tries are laid out depth first like `.tre` files, with each node
+written as an end flag byte, a big endian `u16` child count and the byte
+for each child, followed by the children themselves.
+
+This doesn't implement [`TrieNode`](super::TrieNode), so can't reuse the
+set operations and file format built on it. That trait is fixed to the 26
+letters `a-z`: its methods take children by `char` and walk `'a'..='z'`,
+and both [`TrieNode::child_mask`](super::TrieNode::child_mask) and the
+`.tre` header pack a node's children into 26 bits, which can't describe a
+node with up to 256 children. So the set operations and format here
+mirror those instead.
+*/
+
+use std::{
+  fs,
+  io::{self, ErrorKind},
+  mem,
+  ops::{BitAnd, BitOr, Sub},
+  path::Path,
+  vec,
+};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ByteTrie {
+  is_end: bool,
+  children: Vec<(u8, ByteTrie)>,
+}
+
+impl ByteTrie {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn is_end(&self) -> bool {
+    self.is_end
+  }
+
+  /// Whether the trie holds no keys, not even the empty key.
+  pub fn is_empty(&self) -> bool {
+    !self.is_end && self.children.is_empty()
+  }
+
+  /// The number of keys in the trie.
+  pub fn len(&self) -> usize {
+    let below: usize = self.children.iter().map(|(_, child)| child.len()).sum();
+    below + self.is_end as usize
+  }
+
+  pub fn get_child(&self, byte: u8) -> Option<&Self> {
+    let i = self
+      .children
+      .binary_search_by_key(&byte, |(b, _)| *b)
+      .ok()?;
+    Some(&self.children[i].1)
+  }
+
+  /// The children of this node, in byte order.
+  pub fn children(&self) -> impl Iterator<Item = (u8, &Self)> + '_ {
+    self.children.iter().map(|(byte, child)| (*byte, child))
+  }
+
+  fn get_mut_or_insert(&mut self, byte: u8) -> &mut Self {
+    let i = match self.children.binary_search_by_key(&byte, |(b, _)| *b) {
+      Ok(i) => i,
+      Err(i) => {
+        self.children.insert(i, (byte, Self::new()));
+        i
+      }
+    };
+    &mut self.children[i].1
+  }
+
+  /// The node reached by following a prefix, if the trie has that prefix.
+  pub fn get_node(&self, prefix: impl AsRef<[u8]>) -> Option<&Self> {
+    let mut bytes = prefix.as_ref().iter();
+    bytes.try_fold(self, |node, &byte| node.get_child(byte))
+  }
+
+  pub fn contains(&self, key: impl AsRef<[u8]>) -> bool {
+    self.get_node(key).is_some_and(Self::is_end)
+  }
+
+  /// Inserts a key, returning whether it was new.
+  pub fn insert(&mut self, key: impl AsRef<[u8]>) -> bool {
+    let bytes = key.as_ref().iter();
+    let node = bytes.fold(self, |node, &byte| node.get_mut_or_insert(byte));
+    !mem::replace(&mut node.is_end, true)
+  }
+
+  /// Removes a key, pruning any branches left without keys, returning
+  /// whether it was present.
+  pub fn remove(&mut self, key: impl AsRef<[u8]>) -> bool {
+    let Some((&byte, rest)) = key.as_ref().split_first() else {
+      return mem::replace(&mut self.is_end, false);
+    };
+    let Ok(i) = self.children.binary_search_by_key(&byte, |(b, _)| *b) else {
+      return false;
+    };
+    let removed = self.children[i].1.remove(rest);
+    if self.children[i].1.is_empty() {
+      self.children.remove(i);
+    }
+    removed
+  }
+
+  /// Combines the children of two nodes, dropping any that end up empty.
+  fn merge(
+    &self,
+    other: &Self,
+    is_end: bool,
+    f: impl Fn(Option<&Self>, Option<&Self>) -> Option<Self>,
+  ) -> Self {
+    let mut bytes: Vec<u8> = self
+      .children()
+      .chain(other.children())
+      .map(|(b, _)| b)
+      .collect();
+    bytes.sort_unstable();
+    bytes.dedup();
+
+    let children = bytes.into_iter().filter_map(|byte| {
+      let child = f(self.get_child(byte), other.get_child(byte))?;
+      (!child.is_empty()).then_some((byte, child))
+    });
+    Self {
+      is_end,
+      children: children.collect(),
+    }
+  }
+
+  /// The keys present in both tries.
+  pub fn and(&self, other: &Self) -> Self {
+    self.merge(other, self.is_end & other.is_end, |a, b| Some(a?.and(b?)))
+  }
+
+  /// The keys present in either trie.
+  pub fn or(&self, other: &Self) -> Self {
+    self.merge(other, self.is_end | other.is_end, |a, b| match (a, b) {
+      (Some(a), Some(b)) => Some(a.or(b)),
+      (a, b) => a.or(b).cloned(),
+    })
+  }
+
+  /// The keys present in this trie but not the other.
+  pub fn diff(&self, other: &Self) -> Self {
+    self.merge(other, self.is_end & !other.is_end, |a, b| match b {
+      Some(b) => Some(a?.diff(b)),
+      None => a.cloned(),
+    })
+  }
+
+  /// Iterates over the keys in byte order.
+  pub fn keys(&self) -> impl Iterator<Item = Vec<u8>> + '_ {
+    let mut stack = vec![(self, Vec::new())];
+    std::iter::from_fn(move || loop {
+      let (node, key) = stack.pop()?;
+      let children = node.children.iter().rev();
+      stack.extend(children.map(|(byte, child)| (child, [key.as_slice(), &[*byte]].concat())));
+      if node.is_end {
+        return Some(key);
+      }
+    })
+  }
+
+  /// Iterates over the keys that are valid UTF-8, in byte order.
+  pub fn strings(&self) -> impl Iterator<Item = String> + '_ {
+    self.keys().filter_map(|key| String::from_utf8(key).ok())
+  }
+
+  /// Encodes the trie depth first, in the format described above.
+  pub fn to_bytes(&self) -> Vec<u8> {
+    let mut bytes = vec![];
+    self.write_to(&mut bytes);
+    bytes
+  }
+
+  fn write_to(&self, bytes: &mut Vec<u8>) {
+    bytes.push(self.is_end as u8);
+    bytes.extend((self.children.len() as u16).to_be_bytes());
+    bytes.extend(self.children.iter().map(|(byte, _)| byte));
+    for (_, child) in &self.children {
+      child.write_to(bytes)
+    }
+  }
+
+  /// Decodes a trie written by [`ByteTrie::to_bytes`].
+  pub fn from_bytes(mut bytes: &[u8]) -> io::Result<Self> {
+    let trie = Self::read_from(&mut bytes)?;
+    match bytes.is_empty() {
+      true => Ok(trie),
+      false => Err(io::Error::new(
+        ErrorKind::InvalidData,
+        "trailing bytes after trie",
+      )),
+    }
+  }
+
+  /// Reads a node's end flag and the bytes of its children, leaving the
+  /// children themselves to be read.
+  fn read_header(bytes: &mut &[u8]) -> io::Result<(Self, vec::IntoIter<u8>)> {
+    let mut take = |n: usize| {
+      let (taken, rest) = bytes.split_at_checked(n).ok_or(ErrorKind::UnexpectedEof)?;
+      *bytes = rest;
+      io::Result::Ok(taken)
+    };
+    let is_end = match take(1)? {
+      [0] => false,
+      [1] => true,
+      _ => return Err(io::Error::new(ErrorKind::InvalidData, "invalid end flag")),
+    };
+    let count = u16::from_be_bytes([take(1)?[0], take(1)?[0]]) as usize;
+    let keys = take(count)?.to_vec();
+    if keys.windows(2).any(|pair| pair[0] >= pair[1]) {
+      return Err(io::Error::new(
+        ErrorKind::InvalidData,
+        "children out of order",
+      ));
+    }
+
+    let node = Self {
+      is_end,
+      children: Vec::with_capacity(count),
+    };
+    Ok((node, keys.into_iter()))
+  }
+
+  /// Reads a node and its descendants, keeping the nodes still waiting on
+  /// children on a stack rather than recursing, so that long keys can't
+  /// overflow the stack.
+  fn read_from(bytes: &mut &[u8]) -> io::Result<Self> {
+    // each frame is a node, the children it's still waiting for
+    // and the byte it will be stored under in its parent
+    let (root, pending) = Self::read_header(bytes)?;
+    let mut stack = vec![(root, pending, None)];
+    loop {
+      let (_, pending, _) = stack.last_mut().expect("stack won't be empty");
+      if let Some(byte) = pending.next() {
+        let (node, pending) = Self::read_header(bytes)?;
+        stack.push((node, pending, Some(byte)));
+        continue;
+      }
+
+      let (node, _, byte) = stack.pop().expect("stack won't be empty");
+      match (stack.last_mut(), byte) {
+        (Some((parent, _, _)), Some(byte)) => parent.children.push((byte, node)),
+        _ => return Ok(node),
+      }
+    }
+  }
+
+  pub fn save(&self, path: &Path) -> io::Result<()> {
+    fs::write(path, self.to_bytes())
+  }
+
+  pub fn load(path: &Path) -> io::Result<Self> {
+    Self::from_bytes(&fs::read(path)?)
+  }
+}
+
+impl Drop for ByteTrie {
+  /// Drops the nodes one at a time, as dropping them recursively would
+  /// overflow the stack on long enough keys.
+  fn drop(&mut self) {
+    let mut nodes = mem::take(&mut self.children);
+    while let Some((_, mut node)) = nodes.pop() {
+      nodes.append(&mut node.children);
+    }
+  }
+}
+
+impl<K: AsRef<[u8]>> FromIterator<K> for ByteTrie {
+  fn from_iter<T: IntoIterator<Item = K>>(iter: T) -> Self {
+    let mut trie = ByteTrie::new();
+    for key in iter {
+      trie.insert(key);
+    }
+    trie
+  }
+}
+
+impl BitAnd for &ByteTrie {
+  type Output = ByteTrie;
+  fn bitand(self, rhs: Self) -> Self::Output {
+    self.and(rhs)
+  }
+}
+impl BitOr for &ByteTrie {
+  type Output = ByteTrie;
+  fn bitor(self, rhs: Self) -> Self::Output {
+    self.or(rhs)
+  }
+}
+impl Sub for &ByteTrie {
+  type Output = ByteTrie;
+  fn sub(self, rhs: Self) -> Self::Output {
+    self.diff(rhs)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn unicode() {
+    // any string should be storable, not just a-z
+    let trie: ByteTrie = ["café", "cafe", "naïve", "", "日本"].into_iter().collect();
+    assert_eq!(trie.len(), 5);
+    assert!(trie.contains("café") && trie.contains("") && trie.contains("日本"));
+    assert!(!trie.contains("caf") && !trie.contains("日"));
+    assert_eq!(
+      trie.strings().collect::<Vec<_>>(),
+      ["", "cafe", "café", "naïve", "日本"]
+    );
+  }
+
+  #[test]
+  fn remove() {
+    // removing should prune branches left empty
+    let mut trie: ByteTrie = ["ab", "abc"].into_iter().collect();
+    assert!(trie.remove("abc"));
+    assert!(!trie.remove("abc"));
+    assert_eq!(trie, ["ab"].into_iter().collect());
+    assert!(trie.remove("ab"));
+    assert!(trie.is_empty());
+  }
+
+  #[test]
+  fn set_ops() {
+    let a: ByteTrie = ["a-1", "b.2", "c"].into_iter().collect();
+    let b: ByteTrie = ["b.2", "c", "d"].into_iter().collect();
+    assert_eq!(&a & &b, ["b.2", "c"].into_iter().collect());
+    assert_eq!(&a | &b, ["a-1", "b.2", "c", "d"].into_iter().collect());
+    assert_eq!(&a - &b, ["a-1"].into_iter().collect());
+  }
+
+  #[test]
+  fn round_trip() {
+    let trie: ByteTrie = [&b"\x00\xff"[..], b"\xff", b"ab", b"a"]
+      .into_iter()
+      .collect();
+    let bytes = trie.to_bytes();
+    assert_eq!(ByteTrie::from_bytes(&bytes).unwrap(), trie);
+    assert!(ByteTrie::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    assert!(ByteTrie::from_bytes(&[bytes.as_slice(), &[0]].concat()).is_err());
+  }
+
+  #[test]
+  fn deep_chain() {
+    // a key too long to read recursively should still load
+    let depth = 200_000;
+    let mut bytes = [0, 0, 1, b'a'].repeat(depth);
+    bytes.extend([1, 0, 0]);
+    let trie = ByteTrie::from_bytes(&bytes).unwrap();
+    assert!(trie.contains("a".repeat(depth)));
+    assert!(!trie.contains("a".repeat(depth - 1)));
+  }
+}
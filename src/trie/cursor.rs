@@ -0,0 +1,91 @@
+use super::TrieNode;
+use crate::letter::Letter;
+
+/// A position within a trie that can be moved a letter at a time, for
+/// following input as it's typed rather than searching from the root.
+#[derive(Debug, Clone)]
+pub struct TrieCursor<'a, N: TrieNode> {
+  path: Vec<&'a N>,
+  prefix: String,
+}
+
+impl<'a, N: TrieNode> TrieCursor<'a, N> {
+  /// A cursor at the root of a trie.
+  pub fn new(root: &'a N) -> Self {
+    Self {
+      path: vec![root],
+      prefix: String::new(),
+    }
+  }
+
+  fn node(&self) -> &'a N {
+    self.path[self.path.len() - 1]
+  }
+
+  /// Moves down to the child for a letter, returning whether it exists.
+  ///
+  /// The cursor doesn't move if there is no child for the letter.
+  pub fn descend(&mut self, c: char) -> bool {
+    let Some(child) = self.node().get_child(c) else {
+      return false;
+    };
+    self.path.push(child);
+    self.prefix.push(c);
+    true
+  }
+
+  /// Moves back up a letter, returning the letter that was removed, or
+  /// `None` if the cursor was already at the root.
+  pub fn ascend(&mut self) -> Option<char> {
+    let c = self.prefix.pop()?;
+    self.path.pop();
+    Some(c)
+  }
+
+  /// The letters followed from the root to reach the cursor.
+  pub fn current_prefix(&self) -> &str {
+    &self.prefix
+  }
+
+  /// Whether the current prefix is a word in the trie.
+  pub fn is_word(&self) -> bool {
+    self.node().is_end()
+  }
+
+  /// The letters that can be descended into from here.
+  pub fn available_letters(&self) -> Letter {
+    Letter::from_mask(self.node().child_mask())
+  }
+
+  /// The endings of the longer words below the cursor.
+  pub fn completions(&self) -> impl Iterator<Item = String> + 'a {
+    self.node().strings()
+  }
+}
+
+#[cfg(all(test, feature = "parse"))]
+mod test {
+  use super::*;
+  use crate::trie_of;
+
+  #[test]
+  fn walk() {
+    // the cursor should track the path as it moves up and down
+    let trie = trie_of(&["car", "cat", "cats"]);
+    let mut cursor = TrieCursor::new(&trie);
+    assert!(cursor.descend('c') && cursor.descend('a'));
+    assert!(!cursor.is_word());
+    assert_eq!(cursor.available_letters(), "[rt]".parse().unwrap());
+
+    assert!(!cursor.descend('b'));
+    assert_eq!(cursor.current_prefix(), "ca");
+    assert!(cursor.descend('t') && cursor.is_word());
+    assert_eq!(cursor.completions().collect::<Vec<_>>(), ["s"]);
+
+    assert_eq!(cursor.ascend(), Some('t'));
+    assert_eq!(cursor.ascend(), Some('a'));
+    assert_eq!(cursor.ascend(), Some('c'));
+    assert_eq!(cursor.ascend(), None);
+    assert_eq!(cursor.current_prefix(), "");
+  }
+}
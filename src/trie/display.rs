@@ -0,0 +1,163 @@
+use super::{Trie, TrieNode};
+use std::env;
+
+/// The characters used to draw branches.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RenderStyle {
+  /// Box-drawing characters, e.g. `├┬╸a`.
+  #[default]
+  Unicode,
+  /// Plain ASCII for terminals and logs that mangle box-drawing, e.g. `+-*a`.
+  Ascii,
+}
+
+impl RenderStyle {
+  /// The variable read by [`RenderStyle::from_env`].
+  pub const VAR: &'static str = "SCRABBLE_RENDER_STYLE";
+
+  /// Picks the style named by the `SCRABBLE_RENDER_STYLE` environment
+  /// variable, either `ascii` or `unicode`, defaulting to unicode.
+  pub fn from_env() -> Self {
+    match env::var(Self::VAR) {
+      Ok(style) if style.eq_ignore_ascii_case("ascii") => RenderStyle::Ascii,
+      _ => RenderStyle::Unicode,
+    }
+  }
+
+  /// Branch, last branch, continuing line, leaf, fork, end, not end and
+  /// ellipsis characters.
+  fn glyphs(self) -> [&'static str; 8] {
+    match self {
+      RenderStyle::Unicode => ["├", "└", "│", "─", "┬", "╸", "╴", "…"],
+      RenderStyle::Ascii => ["+", "`", "|", "-", "+", "*", "-", "..."],
+    }
+  }
+}
+
+/// Limits on how much of a trie to draw, for tries too large to print.
+///
+/// Anything cut off is replaced by an ellipsis marker.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DisplayOptions {
+  /// The number of letters deep to draw branches.
+  pub max_depth: Option<usize>,
+  /// The number of children to draw under each node.
+  pub max_children: Option<usize>,
+  /// Whether to show the number of words in each branch.
+  pub show_counts: bool,
+  /// The characters to draw with.
+  pub style: RenderStyle,
+}
+
+fn count<N: TrieNode>(node: &N) -> usize {
+  let children = ('a'..='z').filter_map(|c| node.get_child(c));
+  usize::from(node.is_end()) + children.map(count).sum::<usize>()
+}
+
+fn draw<N: TrieNode>(
+  node: &N,
+  options: &DisplayOptions,
+  depth: usize,
+  prefix: &str,
+  lines: &mut Vec<String>,
+) {
+  let children: Vec<_> = ('a'..='z')
+    .filter_map(|c| Some((c, node.get_child(c)?)))
+    .collect();
+  let shown = options
+    .max_children
+    .map_or(children.len(), |max| max.min(children.len()));
+  let hidden = children.len() - shown;
+  let [tee, corner, pipe, leaf, fork, end, not_end, ellipsis] = options.style.glyphs();
+
+  for (i, &(c, child)) in children[..shown].iter().enumerate() {
+    let (branch, indent) = match i + 1 == shown && hidden == 0 {
+      true => (corner, " "),
+      false => (tee, pipe),
+    };
+    let fork = if child.is_leaf() { leaf } else { fork };
+    let end = if child.is_end() { end } else { not_end };
+    let mut line = format!("{prefix}{branch}{fork}{end}{c}");
+    if options.show_counts {
+      line += &format!(" ({})", count(child));
+    }
+    lines.push(line);
+
+    if child.is_leaf() {
+      continue;
+    }
+    let prefix = format!("{prefix}{indent}");
+    if options.max_depth.is_some_and(|max| depth + 1 >= max) {
+      lines.push(format!("{prefix}{corner}{leaf}{ellipsis}"));
+    } else {
+      draw(child, options, depth + 1, &prefix, lines);
+    }
+  }
+
+  if hidden > 0 {
+    lines.push(format!("{prefix}{corner}{leaf}{ellipsis} {hidden} more"));
+  }
+}
+
+/// Draws the branches of a trie, one letter per line.
+pub(crate) fn branches<N: TrieNode>(node: &N, options: &DisplayOptions) -> String {
+  let mut lines = vec![];
+  draw(node, options, 0, "", &mut lines);
+  lines.join("\n")
+}
+
+impl<N: TrieNode> Trie<N> {
+  /// Draws a view of the trie, truncated by the given options.
+  pub fn display_options(&self, options: DisplayOptions) -> String {
+    branches(&self.0, &options)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::trie_of;
+  use crate::trie_ptr;
+
+  fn words() -> trie_ptr::Trie {
+    trie_of(&["a", "an", "and", "ant", "be", "cat"])
+  }
+
+  #[test]
+  fn full() {
+    // no limits should draw every branch
+    let drawn = branches(&words(), &DisplayOptions::default());
+    assert_eq!(
+      drawn,
+      "├┬╸a\n│└┬╸n\n│ ├─╸d\n│ └─╸t\n├┬╴b\n│└─╸e\n└┬╴c\n └┬╴a\n  └─╸t"
+    );
+  }
+
+  #[test]
+  fn truncated() {
+    // limits should replace hidden branches with ellipses
+    let options = DisplayOptions {
+      max_depth: Some(1),
+      max_children: Some(2),
+      show_counts: true,
+      ..Default::default()
+    };
+    let drawn = branches(&words(), &options);
+    assert_eq!(drawn, "├┬╸a (4)\n│└─…\n├┬╴b (1)\n│└─…\n└─… 1 more");
+  }
+
+  #[test]
+  fn ascii() {
+    // the ascii style should only use ascii characters
+    let options = DisplayOptions {
+      max_children: Some(2),
+      style: RenderStyle::Ascii,
+      ..Default::default()
+    };
+    let drawn = branches(&words(), &options);
+    assert_eq!(
+      drawn,
+      "++*a\n|`+*n\n| +-*d\n| `-*t\n++-b\n|`-*e\n`-... 1 more"
+    );
+  }
+}
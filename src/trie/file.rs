@@ -0,0 +1,181 @@
+/*!
+Loading and saving any [`Trie`] in the same `.tre` and `.txt` formats as
+[`crate::trie_ptr::Trie`], using only [`TrieNode`] methods so that every
+node backend can be persisted and files can move between backends.
+*/
+use super::{Trie, TrieNode};
+use crate::{
+  letter::Letter,
+  trie_ptr::{front_coding, metadata::read_metadata},
+};
+use std::{
+  fs::File,
+  io::{self, BufRead, BufReader, BufWriter, Read, Write},
+  path::Path,
+};
+
+/// The bit set in a node header for nodes that end a word.
+const END: u32 = 1 << 26;
+
+/// Reads a node and its descendants, keeping the nodes still waiting on
+/// children on a stack rather than recursing, so that a file with long
+/// words can't overflow the stack.
+fn read_node<N: TrieNode, R: Read>(reader: &mut R) -> io::Result<N> {
+  let mut read = || {
+    let mut buf = [0; 4];
+    reader.read_exact(&mut buf)?;
+    let header = u32::from_be_bytes(buf);
+    let mut node = N::empty();
+    node.set_end(header & END > 0);
+    io::Result::Ok((node, Letter::from_mask(header)))
+  };
+
+  // each frame is a node, the children it's still waiting for
+  // and the character it will be stored under in its parent
+  let (root, pending) = read()?;
+  let mut stack = vec![(root, pending, None)];
+  loop {
+    let (_, pending, _) = stack.last_mut().expect("stack won't be empty");
+    if let Some(c) = pending.next() {
+      let (node, pending) = read()?;
+      stack.push((node, pending, Some(c)));
+      continue;
+    }
+
+    let (node, _, c) = stack.pop().expect("stack won't be empty");
+    match (stack.last_mut(), c) {
+      (Some((parent, _, _)), Some(c)) => parent.set_child(c, Some(node)),
+      _ => return Ok(node),
+    }
+  }
+}
+
+fn write_node<N: TrieNode, W: Write>(node: &N, writer: &mut W) -> io::Result<()> {
+  let header = if node.is_end() { END } else { 0 } | node.child_mask();
+  writer.write_all(&header.to_be_bytes())?;
+
+  for child in node.children() {
+    write_node(child, writer)?
+  }
+  Ok(())
+}
+
+impl<N: TrieNode> Trie<N> {
+  /// Reads a new trie from a `.tre` or `.txt` file.
+  pub fn file(path: &Path) -> io::Result<Self> {
+    let mut trie = Self::default();
+    trie.load(path)?;
+    Ok(trie)
+  }
+
+  /// Adds the words in a `.tre` or `.txt` file to the trie, picking the
+  /// format from the file extension.
+  pub fn load(&mut self, path: &Path) -> io::Result<()> {
+    let mut reader = BufReader::new(File::open(path)?);
+    match path.extension().and_then(|s| s.to_str()) {
+      Some("tre") => {
+        read_metadata(&mut reader)?;
+        let loaded: N = read_node(&mut reader)?;
+        self.0.or_assign(&loaded);
+        Ok(())
+      }
+      Some("txt") => {
+        for word in front_coding::words(reader.lines()) {
+          *self += word?.as_str();
+        }
+        Ok(())
+      }
+      Some(_) | None => Err(io::Error::new(
+        io::ErrorKind::InvalidData,
+        "File type not supported, please load from either a .tre or .txt file.",
+      )),
+    }
+  }
+
+  /// Saves the trie to a `.tre` or `.txt` file, picking the format from
+  /// the file extension.
+  pub fn save(&self, path: &Path) -> io::Result<()> {
+    let ext = path.extension().and_then(|s| s.to_str());
+    if !matches!(ext, Some("tre" | "txt")) {
+      return Err(io::Error::new(
+        io::ErrorKind::InvalidData,
+        "File type not supported, please save to either a .tre or .txt file.",
+      ));
+    }
+
+    let mut writer = BufWriter::new(File::create(path)?);
+    if ext == Some("tre") {
+      write_node(&self.0, &mut writer)?;
+    } else {
+      for word in self.strings() {
+        writeln!(writer, "{word}")?
+      }
+    }
+    writer.flush()
+  }
+
+  /// The trie in the `.tre` format, without a metadata block.
+  pub fn to_bytes(&self) -> Vec<u8> {
+    let mut bytes = vec![];
+    write_node(&self.0, &mut bytes).expect("writing to a Vec shouldn't fail");
+    bytes
+  }
+
+  /// Reads a trie from bytes in the `.tre` format, skipping any metadata
+  /// block and failing if there's anything after the last node.
+  pub fn from_bytes(mut bytes: &[u8]) -> io::Result<Self> {
+    read_metadata(&mut bytes)?;
+    let trie = Trie(read_node(&mut bytes)?);
+    if !bytes.is_empty() {
+      return Err(io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{} unexpected bytes after the last node.", bytes.len()),
+      ));
+    }
+    Ok(trie)
+  }
+}
+
+#[cfg(all(test, feature = "parse"))]
+mod test {
+  use super::*;
+  use crate::trie_ptr;
+  use std::fs;
+
+  #[test]
+  fn round_trip() {
+    // files should be interchangeable with the pointer trie's
+    let trie: Trie<trie_ptr::Trie> = "c[ao][bt]".parse().unwrap();
+    for ext in ["tre", "txt"] {
+      let path = crate::temp_path(format!("scrabble_generic_file.{ext}"));
+      trie.save(&path).unwrap();
+      let generic = Trie::<trie_ptr::Trie>::file(&path);
+      let pointer = trie_ptr::Trie::file(&path);
+      fs::remove_file(&path).unwrap();
+      assert_eq!(*generic.unwrap(), *trie);
+      assert_eq!(pointer.unwrap(), *trie);
+    }
+
+    let bytes = trie.to_bytes();
+    assert_eq!(
+      bytes,
+      trie_ptr::Trie::from_bytes(&bytes).unwrap().to_bytes()
+    );
+    assert_eq!(*Trie::<trie_ptr::Trie>::from_bytes(&bytes).unwrap(), *trie);
+
+    let path = crate::temp_path("scrabble_generic_file.csv");
+    assert!(trie.save(&path).is_err());
+    assert!(!path.exists());
+  }
+
+  #[test]
+  fn deep_chain() {
+    // a word too long to read recursively should still load
+    let depth = 200_000;
+    let mut bytes = 1u32.to_be_bytes().repeat(depth);
+    bytes.extend(END.to_be_bytes());
+    let trie = Trie::<trie_ptr::Trie>::from_bytes(&bytes).unwrap();
+    assert!(trie.contains_str(&"a".repeat(depth)));
+    assert!(!trie.contains_str(&"a".repeat(depth - 1)));
+  }
+}
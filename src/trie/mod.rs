@@ -1,12 +1,31 @@
-use crate::word::Word;
+use crate::{
+  alphabet::from_index_unchecked,
+  letter::Letter,
+  set::Set,
+  word::{ParseError, Word},
+};
 use std::{
-  ops::{BitAnd, BitOr, Deref, DerefMut, Sub},
+  ops::{
+    Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, Deref, DerefMut, Div, DivAssign, Sub,
+    SubAssign,
+  },
   str::FromStr,
 };
 
+pub mod bytes;
+mod cursor;
+mod display;
+mod file;
 mod node_trait;
-pub use node_trait::TrieNode;
-use nom::error::Error;
+#[cfg(feature = "compact")]
+pub mod packed;
+pub use bytes::ByteTrie;
+pub use cursor::TrieCursor;
+pub(crate) use display::branches;
+pub use display::{DisplayOptions, RenderStyle};
+pub use node_trait::{DepthFirstIterator, StringIterator, TrieNode, WalkError, WalkIterator};
+#[cfg(feature = "compact")]
+pub use packed::PackedNode;
 
 pub struct Trie<N: TrieNode>(N);
 
@@ -21,12 +40,71 @@ impl<N: TrieNode> From<Word> for Trie<N> {
   }
 }
 impl<N: TrieNode> FromStr for Trie<N> {
-  type Err = Error<String>;
+  type Err = ParseError;
   fn from_str(s: &str) -> Result<Self, Self::Err> {
     Ok(Self(N::from_word(s.parse::<Word>()?)))
   }
 }
 
+impl<N: TrieNode> Trie<N> {
+  /// Adds the words matching a pattern, walking straight down the trie
+  /// when the pattern is a single word.
+  fn insert(&mut self, word: Word) {
+    let single = |letter: &Letter| (letter.len() == 1).then(|| letter.peek_idx()).flatten();
+    let chars: Option<Vec<char>> = word
+      .iter()
+      .map(|letter| single(letter).map(from_index_unchecked))
+      .collect();
+    match chars {
+      Some(chars) => {
+        let node = chars
+          .into_iter()
+          .fold(&mut self.0, |node, c| node.get_mut_or_insert(c));
+        node.set_end(true)
+      }
+      None => self.0 = self.0.or(&N::from_word(word)),
+    }
+  }
+}
+
+impl<N: TrieNode> FromIterator<Word> for Trie<N> {
+  fn from_iter<T: IntoIterator<Item = Word>>(iter: T) -> Self {
+    let mut trie = Self::default();
+    trie.extend(iter);
+    trie
+  }
+}
+impl<N: TrieNode> FromIterator<String> for Trie<N> {
+  /// Collects patterns into a trie, see [`Word::parse_lenient`].
+  fn from_iter<T: IntoIterator<Item = String>>(iter: T) -> Self {
+    iter.into_iter().map(|s| Word::parse_lenient(&s)).collect()
+  }
+}
+impl<'a, N: TrieNode> FromIterator<&'a str> for Trie<N> {
+  /// Collects patterns into a trie, see [`Word::parse_lenient`].
+  fn from_iter<T: IntoIterator<Item = &'a str>>(iter: T) -> Self {
+    iter.into_iter().map(Word::parse_lenient).collect()
+  }
+}
+
+impl<N: TrieNode> Extend<Word> for Trie<N> {
+  fn extend<T: IntoIterator<Item = Word>>(&mut self, iter: T) {
+    for word in iter {
+      self.insert(word)
+    }
+  }
+}
+impl<N: TrieNode> Extend<String> for Trie<N> {
+  fn extend<T: IntoIterator<Item = String>>(&mut self, iter: T) {
+    self.extend(iter.into_iter().map(|s| Word::parse_lenient(&s)))
+  }
+}
+impl<'a, N: TrieNode> Extend<&'a str> for Trie<N> {
+  fn extend<T: IntoIterator<Item = &'a str>>(&mut self, iter: T) {
+    self.extend(iter.into_iter().map(Word::parse_lenient))
+  }
+}
+
 impl<N: TrieNode> Deref for Trie<N> {
   type Target = N;
   fn deref(&self) -> &Self::Target {
@@ -39,6 +117,40 @@ impl<N: TrieNode> DerefMut for Trie<N> {
   }
 }
 
+impl<N: TrieNode> BitAndAssign<&Trie<N>> for Trie<N> {
+  fn bitand_assign(&mut self, rhs: &Trie<N>) {
+    self.0.and_assign(&rhs.0)
+  }
+}
+impl<N: TrieNode> BitOrAssign<&Trie<N>> for Trie<N> {
+  fn bitor_assign(&mut self, rhs: &Trie<N>) {
+    self.0.or_assign(&rhs.0)
+  }
+}
+impl<N: TrieNode> SubAssign<&Trie<N>> for Trie<N> {
+  fn sub_assign(&mut self, rhs: &Trie<N>) {
+    self.0.diff_assign(&rhs.0)
+  }
+}
+
+impl<N: TrieNode> DivAssign<&Trie<N>> for Trie<N> {
+  fn div_assign(&mut self, rhs: &Trie<N>) {
+    self.0.diff_assign(&rhs.0)
+  }
+}
+impl<N: TrieNode> AddAssign<&str> for Trie<N> {
+  /// Adds the words matching a pattern, see [`Word::parse_lenient`].
+  fn add_assign(&mut self, rhs: &str) {
+    self.insert(Word::parse_lenient(rhs))
+  }
+}
+impl<N: TrieNode> SubAssign<&str> for Trie<N> {
+  /// Removes the words matching a pattern, see [`Word::parse_lenient`].
+  fn sub_assign(&mut self, rhs: &str) {
+    self.0.diff_assign(&N::from_word(Word::parse_lenient(rhs)))
+  }
+}
+
 impl<N: TrieNode> BitAnd<&Trie<N>> for &Trie<N> {
   type Output = Trie<N>;
   fn bitand(self, rhs: &Trie<N>) -> Self::Output {
@@ -83,3 +195,27 @@ impl<N: TrieNode, I: Into<Word> + Clone> Sub<&I> for &Trie<N> {
     self - &trie
   }
 }
+
+impl<N: TrieNode> Div<&Trie<N>> for &Trie<N> {
+  type Output = Trie<N>;
+  fn div(self, rhs: &Trie<N>) -> Self::Output {
+    Trie(self.diff(rhs.deref()))
+  }
+}
+
+impl<N: TrieNode> Add<&str> for &Trie<N> {
+  type Output = Trie<N>;
+  fn add(self, rhs: &str) -> Self::Output {
+    let mut trie = Trie(self.0.clone());
+    trie += rhs;
+    trie
+  }
+}
+impl<N: TrieNode> Sub<&str> for &Trie<N> {
+  type Output = Trie<N>;
+  fn sub(self, rhs: &str) -> Self::Output {
+    let mut trie = Trie(self.0.clone());
+    trie -= rhs;
+    trie
+  }
+}
@@ -1,12 +1,5 @@
-use crate::word::Word;
-use std::{
-  iter::{Flatten, Scan},
-  ops::RangeInclusive,
-};
-
-type DepthFirstGenerator<T, R> =
-  for<'a, 'b> fn(&'a mut R, (&'b T, Option<char>)) -> Option<Option<R>>;
-type DepthFirstScan<'a, T, R> = Scan<DepthFirstIterator<'a, T>, R, DepthFirstGenerator<T, R>>;
+use crate::{letter::Letter, set::Set, word::Word};
+use std::{error::Error, fmt::Display, iter, ops::RangeInclusive};
 
 pub trait TrieNode: Clone + Sized {
   /*--------------------------------------------------*/
@@ -37,10 +30,7 @@ pub trait TrieNode: Clone + Sized {
     let mut node = Self::empty();
 
     if let Some(letter) = word.pop() {
-      let sub = Self::from_word(word);
-      for c in letter {
-        node.set_child(c, Some(sub.clone()))
-      }
+      node.set_children_from_mask(letter.to_mask(), &Self::from_word(word));
     } else {
       node.set_end(true)
     }
@@ -62,7 +52,7 @@ pub trait TrieNode: Clone + Sized {
         self
           .get_child(c)
           .zip(other.get_child(c))
-          .map_or(false, |(sub0, sub1)| sub0.has_any(sub1))
+          .is_some_and(|(sub0, sub1)| sub0.has_any(sub1))
       })
   }
   /// Whether all of the words in other occur in self
@@ -72,7 +62,7 @@ pub trait TrieNode: Clone + Sized {
         self
           .get_child(c)
           .zip(other.get_child(c))
-          .map_or(false, |(sub0, sub1)| sub0.has_all(sub1))
+          .is_some_and(|(sub0, sub1)| sub0.has_all(sub1))
       })
   }
 
@@ -82,6 +72,21 @@ pub trait TrieNode: Clone + Sized {
       .filter(|&c| self.get_child(c).is_some())
       .collect()
   }
+  /// The characters a node has children for, as a 26 bit mask.
+  ///
+  /// This uses the same layout as [`Letter::to_mask`] and the `.tre` format.
+  fn child_mask(&self) -> u32 {
+    self.chars().into_iter().collect::<Letter>().to_mask()
+  }
+  /// Sets the children of a node to a copy of `template` for every character
+  /// in the mask, removing the children for all other characters.
+  fn set_children_from_mask(&mut self, mask: u32, template: &Self) {
+    let letter = Letter::from_mask(mask);
+    for c in 'a'..='z' {
+      let sub = letter.contains(&c).then(|| template.clone());
+      self.set_child(c, sub)
+    }
+  }
   /// A naive implementation that gets children of a node
   fn children(&self) -> Vec<&Self> {
     ('a'..='z').filter_map(|c| self.get_child(c)).collect()
@@ -108,55 +113,61 @@ pub trait TrieNode: Clone + Sized {
   /// A word will only be included if it is present in both tries.
   fn and(&self, other: &Self) -> Self {
     let mut tree = self.clone();
-    tree.set_end(self.is_end() & other.is_end());
-
-    for c in 'a'..='z' {
-      let sub = tree
-        .get_child(c)
-        .zip(other.get_child(c))
-        .map(|(sub0, sub1)| sub0.and(sub1));
-      tree.set_child(c, sub)
-    }
-
+    tree.and_assign(other);
     tree
   }
   /// The union of two trie nodes.
   ///
   /// A word will be included if it present in either trie.
   fn or(&self, other: &Self) -> Self {
-    let mut tree = Self::empty();
-    tree.set_end(self.is_end() | other.is_end());
-
-    for c in 'a'..='z' {
-      let sub = match (self.get_child(c), other.get_child(c)) {
-        (Some(sub0), Some(sub1)) => Some(sub0.or(sub1)),
-        (Some(sub0), None) => Some(sub0.or(&Self::empty())),
-        (None, Some(sub1)) => Some(sub1.or(&Self::empty())),
-        (None, None) => None,
-      };
-      tree.set_child(c, sub)
-    }
-
+    let mut tree = self.clone();
+    tree.or_assign(other);
     tree
   }
   /// The asymmetric difference of two tries.
   ///
   /// A word will be present if it's in the first but not second trie.
-  ///
   fn diff(&self, other: &Self) -> Self {
-    let mut tree = Self::empty();
-    tree.set_end(tree.is_end() & !other.is_end());
+    let mut tree = self.clone();
+    tree.diff_assign(other);
+    tree
+  }
 
-    for c in 'a'..='z' {
-      let sub = match (self.get_child(c), other.get_child(c)) {
-        (Some(sub0), Some(sub1)) => Some(sub0.diff(sub1)),
-        (Some(sub0), None) => Some(sub0.clone()),
-        (None, _) => None,
+  /// Keeps only the words also in other, in place.
+  fn and_assign(&mut self, other: &Self) {
+    self.set_end(self.is_end() && other.is_end());
+    for c in self.chars() {
+      match other.get_child(c) {
+        Some(sub) => self.get_mut_or_insert(c).and_assign(sub),
+        None => self.set_child(c, None),
+      }
+    }
+  }
+  /// Adds every word in other, in place.
+  fn or_assign(&mut self, other: &Self) {
+    self.set_end(self.is_end() || other.is_end());
+    for c in other.chars() {
+      let sub = other.get_child(c).expect("child should exist for its char");
+      match self.get_mut_child(c) {
+        Some(node) => node.or_assign(sub),
+        None => self.set_child(c, Some(sub.clone())),
+      }
+    }
+  }
+  /// Removes every word in other, in place, dropping any branches that
+  /// are left without words.
+  fn diff_assign(&mut self, other: &Self) {
+    self.set_end(self.is_end() && !other.is_end());
+    for c in other.chars() {
+      let sub = other.get_child(c).expect("child should exist for its char");
+      let Some(node) = self.get_mut_child(c) else {
+        continue;
       };
-      tree.set_child(c, sub)
+      node.diff_assign(sub);
+      if node.is_empty() {
+        self.set_child(c, None)
+      }
     }
-
-    tree
   }
 
   fn dfs(&self) -> DepthFirstIterator<'_, Self> {
@@ -165,66 +176,147 @@ pub trait TrieNode: Clone + Sized {
     }
   }
 
-  // fn words(&self) -> Flatten<DepthFirstScan<'_, Self, Word>> {
-  //   fn scan_to_word<T: TrieNode>(
-  //     word: &mut Word,
-  //     (node, movement): (&T, Option<char>),
-  //   ) -> Option<Option<Word>> {
-  //     match movement {
-  //       Some(c) => word.push_char(c),
-  //       None => {
-  //         word.pop();
-  //       }
-  //     };
-  //     movement.map(|_| node.is_end().then(|| word.clone()))
-  //   }
-
-  //   self
-  //     .dfs()
-  //     .scan(
-  //       "".to_owned(),
-  //       scan_to_word
-  //         as for<'a, 'b> fn(&'a mut Word, (&'b Self, Option<char>)) -> Option<Option<Word>>,
-  //     )
-  //     .flatten()
-  // }
+  /// Generates an iterator over all the words stored in the node, where
+  /// each letter of a word holds a single character.
+  fn words(&self) -> impl Iterator<Item = Word> + '_ {
+    self
+      .dfs()
+      .scan(Word::default(), |word, (node, movement)| {
+        match movement {
+          Some(c) => word.push_char(c),
+          None => {
+            word.pop_back();
+          }
+        };
+        Some(movement.and_then(|_| node.is_end().then(|| word.clone())))
+      })
+      .flatten()
+  }
 
   /// Generates an iterator over all the strings stored in the node
-  ///
-  /// I'm sorry the type for this is just awful to write, but I couldn't use
-  /// `impl Iterator<Item = String>` as that's not supported by rust yet, due
-  /// to Higher Kinded Type (HKT) issues.
-  fn strings(&self) -> Flatten<DepthFirstScan<'_, Self, String>> {
-    fn scan_to_string<T: TrieNode>(
-      string: &mut String,
-      (node, movement): (&T, Option<char>),
-    ) -> Option<Option<String>> {
-      match movement {
-        Some(c) => string.push(c),
-        None => {
-          string.pop();
-        }
+  fn strings(&self) -> impl Iterator<Item = String> + '_ {
+    self.words().flat_map(Word::into_iter)
+  }
+
+  /// Removes every word that doesn't match a predicate, dropping any
+  /// branches left without words, in a single pass over the trie.
+  fn retain<F: FnMut(&str) -> bool>(&mut self, mut f: F) {
+    retain_from(self, &mut String::new(), &mut f);
+  }
+
+  /// A copy of this trie with only the words that match a predicate.
+  fn filtered<F: FnMut(&str) -> bool>(&self, f: F) -> Self {
+    let mut trie = self.clone();
+    trie.retain(f);
+    trie
+  }
+
+  /// Removes every branch that doesn't lead to a word, so that tries with
+  /// the same words have the same shape however they were built.
+  fn canonicalize(&mut self) {
+    for c in 'a'..='z' {
+      let Some(child) = self.get_mut_child(c) else {
+        continue;
       };
-      movement.map(|_| node.is_end().then(|| string.clone()))
+      child.canonicalize();
+      if !child.is_end() && child.children().is_empty() {
+        self.set_child(c, None)
+      }
+    }
+  }
+
+  /// The prefix shared by every word in the trie.
+  fn longest_common_prefix(&self) -> String {
+    let mut prefix = String::new();
+    let mut node = self;
+    while let ([c], false) = (node.chars().as_slice(), node.is_end()) {
+      prefix.push(*c);
+      node = node.get_child(*c).expect("child should exist for its char");
     }
+    prefix
+  }
+
+  /// The longest word in the trie that is a prefix of the given word.
+  fn longest_prefix_of<'w>(&self, word: &'w str) -> Option<&'w str> {
+    let mut longest = self.is_end().then_some(0);
+    let mut node = self;
+    for (i, c) in word.char_indices() {
+      let Some(child) = node.get_child(c) else {
+        break;
+      };
+      node = child;
+      if node.is_end() {
+        longest = Some(i + c.len_utf8())
+      }
+    }
+    longest.map(|end| &word[..end])
+  }
 
+  /// Whether no word in the trie is a prefix of another word in it.
+  fn is_prefix_free(&self) -> bool {
     self
       .dfs()
-      .scan(
-        "".to_owned(),
-        scan_to_string
-          as for<'a, 'b> fn(&'a mut String, (&'b Self, Option<char>)) -> Option<Option<String>>,
-      )
-      .flatten()
+      .all(|(node, _)| !node.is_end() || node.children().is_empty())
+  }
+
+  /// Walks this trie and another side by side, depth first, yielding the
+  /// letter and both children for every branch found in either trie.
+  ///
+  /// Branches that only one trie has are still walked, with `None` for the
+  /// missing side, unless skipped with [`WalkIterator::skip_children`].
+  fn walk_with<'a>(&'a self, other: &'a Self) -> WalkIterator<'a, Self> {
+    WalkIterator {
+      stack: vec![(Some(self), Some(other), 'a'..='z')],
+      prefix: String::new(),
+    }
   }
 
   /// Generates an iterator over references to the tries that occur at the end
   /// of each word in other, or an error if the trie doesn't extend that far.
-  fn extract<'a>(&'a self, other: &'a Self) -> ExtractIterator<'_, Self> {
-    ExtractIterator {
-      stack: vec![(self, other, 'a'..='z')],
+  fn extract<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = Result<&'a Self, WalkError>> {
+    let root = other.is_end().then_some(Ok(self));
+    let mut walk = self.walk_with(other);
+    let rest = iter::from_fn(move || loop {
+      match walk.next()? {
+        (_, Some(node), Some(end)) if end.is_end() => return Some(Ok(node)),
+        (_, Some(_), Some(_)) => continue,
+        (_, None, Some(_)) => {
+          let prefix = walk.prefix().to_owned();
+          walk.skip_children();
+          return Some(Err(WalkError { prefix }));
+        }
+        (_, _, None) => walk.skip_children(),
+      }
+    });
+    root.into_iter().chain(rest)
+  }
+}
+
+/// Retains words under a node, returning whether any are left.
+fn retain_from<N: TrieNode, F: FnMut(&str) -> bool>(
+  node: &mut N,
+  prefix: &mut String,
+  f: &mut F,
+) -> bool {
+  if node.is_end() && !f(prefix) {
+    node.set_end(false)
+  }
+
+  let mut any = node.is_end();
+  for c in 'a'..='z' {
+    let Some(child) = node.get_mut_child(c) else {
+      continue;
+    };
+    prefix.push(c);
+    let kept = retain_from(child, prefix, f);
+    prefix.pop();
+
+    if !kept {
+      node.set_child(c, None)
     }
+    any |= kept;
   }
+  any
 }
 
 #[derive(Clone)]
@@ -236,7 +328,7 @@ impl<'a, T: TrieNode> Iterator for DepthFirstIterator<'a, T> {
   type Item = (&'a T, Option<char>);
   fn next(&mut self) -> Option<Self::Item> {
     let (node, left) = self.stack.last_mut()?;
-    let entry = left.next().and_then(|c| {
+    let entry = left.find_map(|c| {
       let sub = node.get_child(c)?;
       Some((sub, c))
     });
@@ -282,29 +374,57 @@ impl<'a, T: TrieNode> Iterator for StringIterator<'a, T> {
 }
 
 #[derive(Clone)]
-pub struct ExtractIterator<'a, T: TrieNode> {
-  stack: Vec<(&'a T, &'a T, RangeInclusive<char>)>,
+pub struct WalkIterator<'a, T: TrieNode> {
+  stack: Vec<(Option<&'a T>, Option<&'a T>, RangeInclusive<char>)>,
+  prefix: String,
 }
 
-impl<'a, T: TrieNode> Iterator for ExtractIterator<'a, T> {
-  type Item = Result<&'a T, String>;
-  fn next(&mut self) -> Option<Self::Item> {
-    let (tree0, tree1, mut left) = self.stack.pop()?;
-    if tree0.is_end() & tree1.is_end() {
-      return Some(Ok(tree0));
-    }
-    if left.is_empty() {
-      return self.next();
+impl<T: TrieNode> WalkIterator<'_, T> {
+  /// The letters leading to the branch last yielded.
+  pub fn prefix(&self) -> &str {
+    &self.prefix
+  }
+
+  /// Skips over the branches below the one last yielded.
+  pub fn skip_children(&mut self) {
+    if let Some((_, _, left)) = self.stack.last_mut() {
+      left.for_each(drop)
     }
+  }
+}
 
-    let c = left.next().unwrap();
-    self.stack.push((tree0, tree1, left));
-    let optn = tree0.get_child(c).zip(tree1.get_child(c));
-    if let Some((sub0, sub1)) = optn {
-      self.stack.push((sub0, sub1, 'a'..='z'));
-      self.next()
-    } else {
-      Some(Err(format!("Cannot find child for `{c}`")))
+impl<'a, T: TrieNode> Iterator for WalkIterator<'a, T> {
+  type Item = (char, Option<&'a T>, Option<&'a T>);
+  fn next(&mut self) -> Option<Self::Item> {
+    loop {
+      let (tree0, tree1, left) = self.stack.last_mut()?;
+      let Some(c) = left.next() else {
+        self.stack.pop();
+        self.prefix.pop();
+        continue;
+      };
+
+      let sub0 = tree0.and_then(|node| node.get_child(c));
+      let sub1 = tree1.and_then(|node| node.get_child(c));
+      if sub0.is_some() || sub1.is_some() {
+        self.prefix.push(c);
+        self.stack.push((sub0, sub1, 'a'..='z'));
+        return Some((c, sub0, sub1));
+      }
     }
   }
 }
+
+/// A branch of one trie that another trie doesn't extend to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WalkError {
+  pub prefix: String,
+}
+
+impl Display for WalkError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "Cannot find child for `{}`", self.prefix)
+  }
+}
+
+impl Error for WalkError {}
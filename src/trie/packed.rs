@@ -0,0 +1,136 @@
+/*!
+A trie node that only stores the children it has.
+
+Each node keeps a bitmask of the letters it has children for, with the
+children themselves packed in letter order. A child's position is the
+number of set bits below its letter. Nodes with one or two children, the
+bulk of a natural language dictionary, keep them inline without a separate
+allocation for the list.
+
+This is only a node type: the `compact` feature that enables it leaves
+[`trie_ptr::Trie`](crate::trie_ptr::Trie) as it is. A packed dictionary is
+opt in, through [`Trie<PackedNode>`](super::Trie), which reads and writes
+the same `.tre` files.
+*/
+
+use super::TrieNode;
+use crate::alphabet::into_index;
+use smallvec::SmallVec;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PackedNode {
+  is_end: bool,
+  mask: u32,
+  children: SmallVec<[Box<PackedNode>; 2]>,
+}
+
+impl PackedNode {
+  /// The position of a letter's child in the packed list.
+  fn position(&self, i: usize) -> usize {
+    (self.mask & ((1 << i) - 1)).count_ones() as usize
+  }
+
+  /// The position of a letter's child, if there is one.
+  fn find(&self, c: char) -> Option<usize> {
+    let i = into_index(c)?;
+    (self.mask & (1 << i) != 0).then(|| self.position(i))
+  }
+}
+
+impl TrieNode for PackedNode {
+  fn empty() -> Self {
+    Self::default()
+  }
+
+  fn is_end(&self) -> bool {
+    self.is_end
+  }
+  fn set_end(&mut self, end: bool) {
+    self.is_end = end
+  }
+  fn is_leaf(&self) -> bool {
+    self.mask == 0
+  }
+
+  fn get_child(&self, c: char) -> Option<&Self> {
+    Some(&self.children[self.find(c)?])
+  }
+  fn get_mut_child(&mut self, c: char) -> Option<&mut Self> {
+    let position = self.find(c)?;
+    Some(&mut self.children[position])
+  }
+  fn set_child(&mut self, c: char, other: Option<Self>) {
+    let Some(i) = into_index(c) else { return };
+    let position = self.position(i);
+    let present = self.mask & (1 << i) != 0;
+    match (other, present) {
+      (Some(child), true) => *self.children[position] = child,
+      (Some(child), false) => {
+        self.children.insert(position, Box::new(child));
+        self.mask |= 1 << i
+      }
+      (None, true) => {
+        self.children.remove(position);
+        self.mask &= !(1 << i)
+      }
+      (None, false) => {}
+    }
+  }
+
+  fn child_mask(&self) -> u32 {
+    self.mask
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::trie_ptr::Trie;
+  use std::mem;
+
+  #[test]
+  fn matches_trie() {
+    // packed nodes should hold the same words as the pointer trie
+    let a = PackedNode::from_word("c[ao][tb]".parse().unwrap());
+    let b = PackedNode::from_word("[bc]at".parse().unwrap());
+    let words = |node: PackedNode| node.strings().collect::<Vec<_>>();
+    assert_eq!(words(a.clone()), Trie::str("c[ao][tb]").collect::<Vec<_>>());
+    assert_eq!(words(a.and(&b)), ["cat"]);
+    assert_eq!(words(a.or(&b)), ["bat", "cab", "cat", "cob", "cot"]);
+    assert_eq!(words(a.diff(&b)), ["cab", "cob", "cot"]);
+
+    let mut c = a.clone();
+    c.and_assign(&b);
+    assert_eq!(words(c), ["cat"]);
+  }
+
+  #[test]
+  fn set_child() {
+    // children should stay in letter order as they're added and removed
+    let mut node = PackedNode::empty();
+    for c in ['m', 'c', 'x'] {
+      node.set_child(c, Some(PackedNode::from_word("a".parse().unwrap())));
+    }
+    assert_eq!(node.chars(), ['c', 'm', 'x']);
+    node.set_child('m', None);
+    assert_eq!(node.chars(), ['c', 'x']);
+    assert!(node
+      .get_child('x')
+      .is_some_and(|child| child.get_child('a').is_some()));
+  }
+
+  #[test]
+  fn opt_in() {
+    // a packed trie should read the same files as the pointer trie
+    let words = crate::trie_of(&["at", "cat", "cats"]);
+    let packed = crate::trie::Trie::<PackedNode>::from_bytes(&words.to_bytes()).unwrap();
+    assert_eq!(packed.strings().collect::<Vec<_>>(), ["at", "cat", "cats"]);
+    assert_eq!(packed.to_bytes(), words.to_bytes());
+  }
+
+  #[test]
+  fn smaller() {
+    // a node should be several times smaller than an array of children
+    assert!(mem::size_of::<PackedNode>() * 4 <= mem::size_of::<Trie>());
+  }
+}
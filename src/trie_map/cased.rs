@@ -0,0 +1,111 @@
+use super::TrieMap;
+
+/// A set of words that looks words up ignoring case, but remembers how
+/// each was originally written, e.g. for lists of proper nouns.
+///
+/// Every spelling folding to the same key is kept, in insertion order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CasedTrie {
+  spellings: TrieMap<Vec<String>>,
+}
+
+/// The key a word is stored under, if it only contains ascii letters.
+fn fold(word: &str) -> Option<String> {
+  word
+    .chars()
+    .all(|c| c.is_ascii_alphabetic())
+    .then(|| word.to_ascii_lowercase())
+}
+
+impl CasedTrie {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Inserts a spelling of a word, returning whether it was new.
+  ///
+  /// # Panics
+  /// If the word contains characters outside of `a-z` and `A-Z`.
+  pub fn insert(&mut self, word: &str) -> bool {
+    let key = fold(word).expect("words should only contain ascii letters");
+    match self.spellings.get_mut(&key) {
+      Some(spellings) if spellings.iter().any(|s| s == word) => false,
+      Some(spellings) => {
+        spellings.push(word.to_owned());
+        true
+      }
+      None => {
+        self.spellings.insert(&key, vec![word.to_owned()]);
+        true
+      }
+    }
+  }
+
+  /// Whether any spelling of the word is present, ignoring case.
+  pub fn has(&self, word: &str) -> bool {
+    fold(word).is_some_and(|key| self.spellings.contains_key(&key))
+  }
+
+  /// The original spellings of a word, ignoring case in the lookup.
+  pub fn spellings(&self, word: &str) -> &[String] {
+    let spellings = fold(word).and_then(|key| self.spellings.get(&key));
+    spellings.map_or(&[], Vec::as_slice)
+  }
+
+  /// The number of distinct spellings.
+  pub fn len(&self) -> usize {
+    self.spellings.iter().map(|(_, s)| s.len()).sum()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.spellings.is_empty()
+  }
+
+  /// Iterates over the original spellings, ordered by their folded form.
+  pub fn strings(&self) -> impl Iterator<Item = &str> + '_ {
+    let spellings = self.spellings.iter().flat_map(|(_, s)| s);
+    spellings.map(String::as_str)
+  }
+}
+
+impl FromIterator<String> for CasedTrie {
+  fn from_iter<T: IntoIterator<Item = String>>(iter: T) -> Self {
+    let mut trie = CasedTrie::new();
+    for word in iter {
+      trie.insert(&word);
+    }
+    trie
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn preserves_case() {
+    // lookups should fold case, whilst iteration gives the originals
+    let trie: CasedTrie = ["Paris", "london", "Nice", "nice"]
+      .map(String::from)
+      .into_iter()
+      .collect();
+    assert!(trie.has("paris") && trie.has("PARIS") && trie.has("London"));
+    assert!(!trie.has("rome") && !trie.has("new york"));
+    assert_eq!(trie.spellings("NICE"), ["Nice", "nice"]);
+    assert_eq!(trie.len(), 4);
+    assert_eq!(
+      trie.strings().collect::<Vec<_>>(),
+      ["london", "Nice", "nice", "Paris"]
+    );
+  }
+
+  #[test]
+  fn duplicates() {
+    // reinserting the same spelling shouldn't add it twice
+    let mut trie = CasedTrie::new();
+    assert!(trie.insert("Paris"));
+    assert!(!trie.insert("Paris"));
+    assert!(trie.insert("PARIS"));
+    assert_eq!(trie.spellings("paris"), ["Paris", "PARIS"]);
+  }
+}
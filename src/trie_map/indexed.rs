@@ -0,0 +1,207 @@
+use super::index;
+use crate::alphabet::{from_index_unchecked, into_index, SIZE};
+
+/// A node that counts the words below it, so words can be ranked.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Node {
+  id: Option<usize>,
+  count: usize,
+  children: [Option<Box<Node>>; SIZE],
+}
+
+/// A set of words where each word is given a dense id as it's inserted.
+///
+/// Ids count up from zero in insertion order and never change, so data for
+/// each word can be kept in plain `Vec`s indexed by id. Each node also
+/// counts the words below it, so a word's alphabetical rank can be found
+/// by summing the counts along its path.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IndexedTrie {
+  root: Node,
+  words: Vec<String>,
+}
+
+impl IndexedTrie {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  fn node(&self, word: &str) -> Option<&Node> {
+    let mut node = &self.root;
+    for c in word.chars() {
+      node = node.children[into_index(c)?].as_deref()?;
+    }
+    Some(node)
+  }
+
+  /// Inserts a word, returning its id, which is unchanged if the word
+  /// was already present.
+  ///
+  /// # Panics
+  /// If the word contains characters outside of `a-z`.
+  pub fn insert(&mut self, word: &str) -> usize {
+    let indices: Vec<_> = word.chars().map(index).collect();
+    if let Some(id) = self.word_id(word) {
+      return id;
+    }
+    let id = self.words.len();
+    let mut node = &mut self.root;
+    node.count += 1;
+    for i in indices {
+      node = node.children[i].get_or_insert_with(Box::default);
+      node.count += 1;
+    }
+    node.id = Some(id);
+    self.words.push(word.to_owned());
+    id
+  }
+
+  /// The id of a word, if it has been inserted.
+  pub fn word_id(&self, word: &str) -> Option<usize> {
+    self.node(word)?.id
+  }
+
+  /// The word with an id, if there is one.
+  pub fn word_by_id(&self, id: usize) -> Option<&str> {
+    self.words.get(id).map(String::as_str)
+  }
+
+  /// The alphabetical rank of a word, i.e. the number of words before it.
+  ///
+  /// Unlike ids, ranks shift as words are inserted before them.
+  pub fn rank(&self, word: &str) -> Option<usize> {
+    let mut rank = 0;
+    let mut node = &self.root;
+    for c in word.chars() {
+      let i = into_index(c)?;
+      rank += usize::from(node.id.is_some());
+      rank += node.children[..i]
+        .iter()
+        .flatten()
+        .map(|child| child.count)
+        .sum::<usize>();
+      node = node.children[i].as_deref()?;
+    }
+    node.id.map(|_| rank)
+  }
+
+  /// The word with an alphabetical rank, if there is one.
+  pub fn word_by_rank(&self, mut rank: usize) -> Option<String> {
+    let mut word = String::new();
+    let mut node = &self.root;
+    'walk: while rank < node.count {
+      if node.id.is_some() {
+        if rank == 0 {
+          return Some(word);
+        }
+        rank -= 1;
+      }
+      for (i, child) in node.children.iter().enumerate() {
+        let Some(child) = child else { continue };
+        if rank < child.count {
+          word.push(from_index_unchecked(i));
+          node = child;
+          continue 'walk;
+        }
+        rank -= child.count;
+      }
+    }
+    None
+  }
+
+  pub fn contains(&self, word: &str) -> bool {
+    self.word_id(word).is_some()
+  }
+
+  pub fn len(&self) -> usize {
+    self.words.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.words.is_empty()
+  }
+
+  /// Iterates over the words in insertion order, i.e. by id.
+  pub fn iter(&self) -> impl Iterator<Item = &str> + '_ {
+    self.words.iter().map(String::as_str)
+  }
+
+  /// Iterates over the words in alphabetical order, i.e. by rank.
+  pub fn iter_sorted(&self) -> impl Iterator<Item = String> + '_ {
+    (0..self.len()).filter_map(|rank| self.word_by_rank(rank))
+  }
+}
+
+impl FromIterator<String> for IndexedTrie {
+  fn from_iter<T: IntoIterator<Item = String>>(iter: T) -> Self {
+    let mut trie = IndexedTrie::new();
+    for word in iter {
+      trie.insert(&word);
+    }
+    trie
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn ids() {
+    // ids should follow insertion order and survive later inserts
+    let mut trie = IndexedTrie::new();
+    assert_eq!(trie.insert("dog"), 0);
+    assert_eq!(trie.insert("cat"), 1);
+    assert_eq!(trie.insert("ant"), 2);
+    assert_eq!(trie.insert("an"), 3);
+    assert_eq!(trie.insert("do"), 4);
+    assert_eq!(trie.word_id("dog"), Some(0));
+    assert_eq!(trie.word_id("cat"), Some(1));
+    assert_eq!(trie.word_by_id(3), Some("an"));
+    assert_eq!(
+      trie.iter().collect::<Vec<_>>(),
+      ["dog", "cat", "ant", "an", "do"]
+    );
+    for (id, word) in trie.iter().enumerate() {
+      assert_eq!(trie.word_id(word), Some(id));
+    }
+  }
+
+  #[test]
+  fn ranks() {
+    // ranks should follow alphabetical order, not insertion order
+    let trie: IndexedTrie = ["dog", "cat", "ant", "an", "do"]
+      .map(String::from)
+      .into_iter()
+      .collect();
+    assert_eq!(trie.rank("an"), Some(0));
+    assert_eq!(trie.rank("dog"), Some(4));
+    assert_eq!(trie.word_by_rank(2).as_deref(), Some("cat"));
+    assert_eq!(
+      trie.iter_sorted().collect::<Vec<_>>(),
+      ["an", "ant", "cat", "do", "dog"]
+    );
+    for (rank, word) in trie.iter_sorted().enumerate() {
+      assert_eq!(trie.rank(&word), Some(rank));
+    }
+  }
+
+  #[test]
+  fn stable() {
+    // reinserting a word shouldn't change its id
+    let mut trie = IndexedTrie::new();
+    assert_eq!(trie.insert("cat"), 0);
+    assert_eq!(trie.insert("dog"), 1);
+    assert_eq!(trie.insert("cat"), 0);
+    // nor should inserting a word that sorts before it
+    assert_eq!(trie.insert("ant"), 2);
+    assert_eq!(trie.word_id("cat"), Some(0));
+    assert_eq!(trie.word_id("dog"), Some(1));
+    assert_eq!(trie.len(), 3);
+    assert_eq!(trie.word_id("rat"), None);
+    assert_eq!(trie.word_id("ca"), None);
+    assert_eq!(trie.rank("ca"), None);
+    assert_eq!(trie.word_by_id(3), None);
+    assert_eq!(trie.word_by_rank(3), None);
+  }
+}
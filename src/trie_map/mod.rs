@@ -0,0 +1,204 @@
+/*!
+A trie that stores a value at the end of each word, for attaching data
+such as ids, definitions or weights to the words of a dictionary.
+*/
+
+mod cased;
+mod indexed;
+pub use cased::CasedTrie;
+pub use indexed::IndexedTrie;
+
+use crate::alphabet::{from_index_unchecked, into_index, SIZE};
+use std::array;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrieMap<V> {
+  value: Option<V>,
+  children: [Option<Box<TrieMap<V>>>; SIZE],
+}
+
+impl<V> Default for TrieMap<V> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+fn index(c: char) -> usize {
+  into_index(c).unwrap_or_else(|| panic!("`{c}` is not in a-z"))
+}
+
+impl<V> TrieMap<V> {
+  pub fn new() -> Self {
+    TrieMap {
+      value: None,
+      children: array::from_fn(|_| None),
+    }
+  }
+
+  fn node(&self, word: &str) -> Option<&Self> {
+    word
+      .chars()
+      .try_fold(self, |node, c| node.children[into_index(c)?].as_deref())
+  }
+
+  fn node_mut(&mut self, word: &str) -> Option<&mut Self> {
+    word
+      .chars()
+      .try_fold(self, |node, c| node.children[into_index(c)?].as_deref_mut())
+  }
+
+  /// The value stored for a word.
+  pub fn get(&self, word: &str) -> Option<&V> {
+    self.node(word)?.value.as_ref()
+  }
+
+  pub fn get_mut(&mut self, word: &str) -> Option<&mut V> {
+    self.node_mut(word)?.value.as_mut()
+  }
+
+  pub fn contains_key(&self, word: &str) -> bool {
+    self.get(word).is_some()
+  }
+
+  /// Stores a value for a word, returning the value previously stored.
+  ///
+  /// # Panics
+  /// If the word contains characters outside of `a-z`.
+  pub fn insert(&mut self, word: &str, value: V) -> Option<V> {
+    let node = word.chars().fold(self, |node, c| {
+      node.children[index(c)].get_or_insert_with(Box::default)
+    });
+    node.value.replace(value)
+  }
+
+  /// Removes the value for a word, pruning any branches left empty.
+  pub fn remove(&mut self, word: &str) -> Option<V> {
+    let Some(c) = word.chars().next() else {
+      return self.value.take();
+    };
+    let child = self.children[into_index(c)?].as_mut()?;
+    let value = child.remove(&word[c.len_utf8()..]);
+    if child.is_empty() {
+      self.children[index(c)] = None
+    }
+    value
+  }
+
+  /// The number of words with values.
+  pub fn len(&self) -> usize {
+    let children = self.children.iter().flatten();
+    usize::from(self.value.is_some()) + children.map(|child| child.len()).sum::<usize>()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.value.is_none() && self.children.iter().flatten().all(|child| child.is_empty())
+  }
+
+  /// Iterates over words and their values in alphabetical order.
+  pub fn iter(&self) -> Iter<'_, V> {
+    Iter {
+      stack: vec![(self, None)],
+      prefix: String::new(),
+    }
+  }
+}
+
+impl<V> FromIterator<(String, V)> for TrieMap<V> {
+  fn from_iter<T: IntoIterator<Item = (String, V)>>(iter: T) -> Self {
+    let mut map = TrieMap::new();
+    for (word, value) in iter {
+      map.insert(&word, value);
+    }
+    map
+  }
+}
+
+#[derive(Clone)]
+pub struct Iter<'a, V> {
+  /// Each node on the path to the current prefix, with the index of the
+  /// next child to visit, or `None` if its own value hasn't been visited.
+  stack: Vec<(&'a TrieMap<V>, Option<usize>)>,
+  prefix: String,
+}
+
+impl<'a, V> Iterator for Iter<'a, V> {
+  type Item = (String, &'a V);
+  fn next(&mut self) -> Option<Self::Item> {
+    loop {
+      let (node, next) = self.stack.last_mut()?;
+      let node: &'a TrieMap<V> = node;
+      let Some(i) = next else {
+        *next = Some(0);
+        match &node.value {
+          Some(value) => return Some((self.prefix.clone(), value)),
+          None => continue,
+        }
+      };
+
+      if *i == SIZE {
+        self.stack.pop();
+        self.prefix.pop();
+        continue;
+      }
+      let c = *i;
+      *i += 1;
+      if let Some(child) = node.children[c].as_deref() {
+        self.prefix.push(from_index_unchecked(c));
+        self.stack.push((child, None));
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn map() -> TrieMap<usize> {
+    ["cat", "ca", "dog", ""]
+      .into_iter()
+      .enumerate()
+      .map(|(i, word)| (word.to_owned(), i))
+      .collect()
+  }
+
+  #[test]
+  fn get() {
+    // values should only be found for inserted words
+    let map = map();
+    assert_eq!(map.get("cat"), Some(&0));
+    assert_eq!(map.get(""), Some(&3));
+    assert_eq!(map.get("c"), None);
+    assert_eq!(map.get("cats"), None);
+    assert_eq!(map.get("CAT"), None);
+  }
+
+  #[test]
+  fn insert() {
+    // inserting should replace any previous value
+    let mut map = map();
+    assert_eq!(map.insert("cat", 5), Some(0));
+    assert_eq!(map.insert("cow", 6), None);
+    assert_eq!(map.get("cat"), Some(&5));
+    assert_eq!(map.len(), 5);
+  }
+
+  #[test]
+  fn remove() {
+    // removing should prune branches that are left empty
+    let mut map = map();
+    assert_eq!(map.remove("dog"), Some(2));
+    assert_eq!(map.remove("dog"), None);
+    assert!(map.children[index('d')].is_none());
+    assert_eq!(map.remove("ca"), Some(1));
+    assert_eq!(map.get("cat"), Some(&0));
+  }
+
+  #[test]
+  fn iter() {
+    // words should be visited alphabetically
+    let words: Vec<_> = map().iter().map(|(word, &i)| (word, i)).collect();
+    let expected = [("", 3), ("ca", 1), ("cat", 0), ("dog", 2)];
+    assert_eq!(words, expected.map(|(w, i)| (w.to_owned(), i)));
+  }
+}
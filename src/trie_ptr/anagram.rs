@@ -0,0 +1,236 @@
+use super::Trie;
+use crate::{
+  alphabet::{from_index_unchecked, into_index, SIZE},
+  letter::LetterCounts,
+  word::Word,
+};
+use std::ops::RangeBounds;
+
+/// The tiles left in a rack, with `?` standing for a blank.
+#[derive(Debug, Clone, Copy, Default)]
+struct Tiles {
+  counts: [usize; SIZE],
+  blanks: usize,
+}
+
+impl Tiles {
+  fn parse(rack: &str) -> Option<Self> {
+    let mut tiles = Self::default();
+    for c in rack.chars() {
+      match c {
+        '?' => tiles.blanks += 1,
+        c => tiles.counts[into_index(c)?] += 1,
+      }
+    }
+    Some(tiles)
+  }
+
+  fn counted(counts: &LetterCounts) -> Self {
+    Tiles {
+      counts: *counts.counts(),
+      blanks: 0,
+    }
+  }
+
+  fn is_empty(&self) -> bool {
+    self.blanks == 0 && self.counts.iter().all(|&n| n == 0)
+  }
+
+  /// Uses up a tile for the letter at an index, preferring natural tiles.
+  ///
+  /// A blank can stand in for any natural tile, so using the natural tile
+  /// first never rules out a word and avoids finding words twice.
+  fn take(&self, i: usize) -> Option<Self> {
+    let mut tiles = *self;
+    if tiles.counts[i] > 0 {
+      tiles.counts[i] -= 1
+    } else if tiles.blanks > 0 {
+      tiles.blanks -= 1
+    } else {
+      return None;
+    }
+    Some(tiles)
+  }
+}
+
+/// Constraints on the words found by [`Trie::anagrams_with`].
+#[derive(Debug, Clone, Default)]
+pub struct AnagramOptions {
+  /// Whether words may use just some of the tiles, rather than all of them.
+  pub partial: bool,
+  /// The shortest word to find.
+  pub min_len: usize,
+  /// The longest word to find, if there is a limit.
+  pub max_len: Option<usize>,
+  /// Letters every word must contain, whether from natural tiles or blanks.
+  pub must_contain: LetterCounts,
+  /// A pattern every word must match, which also fixes its length.
+  pub pattern: Option<Word>,
+}
+
+impl AnagramOptions {
+  /// Whether a word could still be extended with the letter at an index.
+  fn allows(&self, depth: usize, i: usize) -> bool {
+    self.max_len.is_none_or(|max| depth < max)
+      && self
+        .pattern
+        .as_ref()
+        .is_none_or(|pattern| pattern.get(depth).is_some_and(|letter| letter.has_idx(i)))
+  }
+
+  /// Whether a finished word meets the constraints on the whole word.
+  fn accepts(&self, word: &str) -> bool {
+    word.len() >= self.min_len
+      && self
+        .pattern
+        .as_ref()
+        .is_none_or(|pattern| pattern.len() == word.len())
+      && LetterCounts::from_text(word).covers(&self.must_contain)
+  }
+}
+
+impl Trie {
+  /// Finds words spelt by the tiles that meet the options.
+  fn anagrams_from(
+    &self,
+    tiles: Tiles,
+    options: &AnagramOptions,
+    prefix: &mut String,
+    words: &mut Vec<String>,
+  ) {
+    let used_enough = tiles.is_empty() || options.partial && !prefix.is_empty();
+    if self.is_end && used_enough && options.accepts(prefix) {
+      words.push(prefix.clone())
+    }
+    if tiles.is_empty() {
+      return;
+    }
+
+    for (i, child) in self.children.iter().enumerate() {
+      if !options.allows(prefix.len(), i) {
+        continue;
+      }
+      let sub = child.as_ref().zip(tiles.take(i));
+      if let Some((trie, tiles)) = sub {
+        prefix.push(from_index_unchecked(i));
+        trie.anagrams_from(tiles, options, prefix, words);
+        prefix.pop();
+      }
+    }
+  }
+
+  /// The words spelt from the tiles in a rack that meet the options, in
+  /// order, or `None` if the rack contains anything but letters and `?`.
+  pub fn anagrams_with(&self, rack: &str, options: &AnagramOptions) -> Option<Vec<String>> {
+    let mut words = vec![];
+    self.anagrams_from(Tiles::parse(rack)?, options, &mut String::new(), &mut words);
+    Some(words)
+  }
+
+  /// Finds words spelt with tiles around the fixed letters, where `fixed`
+  /// holds the letter index at each position that has one.
+  fn through_from(
+    &self,
+    tiles: Tiles,
+    fixed: &[Option<usize>],
+    lengths: &impl RangeBounds<usize>,
+    used: bool,
+    prefix: &mut String,
+    words: &mut Vec<String>,
+  ) {
+    let depth = prefix.len();
+    // stopping short of a fixed letter would leave the word running into it
+    let covers_fixed = depth >= fixed.len();
+    if self.is_end && used && covers_fixed && lengths.contains(&depth) {
+      words.push(prefix.clone())
+    }
+
+    let mut descend = |i: usize, tiles: Tiles, used: bool| {
+      if let Some(trie) = &self.children[i] {
+        prefix.push(from_index_unchecked(i));
+        trie.through_from(tiles, fixed, lengths, used, prefix, words);
+        prefix.pop();
+      }
+    };
+    match fixed.get(depth) {
+      Some(&Some(i)) => descend(i, tiles, used),
+      _ => {
+        for i in 0..SIZE {
+          if let Some(tiles) = tiles.take(i) {
+            descend(i, tiles, true)
+          }
+        }
+      }
+    }
+  }
+
+  /// The words that can be played through letters already on the board,
+  /// using at least one tile from the rack, in order.
+  ///
+  /// `fixed` holds the board letters by their position from the start of
+  /// the word. Words must reach past every fixed letter, and lengths
+  /// outside of the range are skipped. Returns `None` if the rack or the
+  /// fixed letters contain anything unexpected.
+  pub fn anagrams_through(
+    &self,
+    rack: &str,
+    fixed: &[(usize, char)],
+    lengths: impl RangeBounds<usize>,
+  ) -> Option<Vec<String>> {
+    let len = fixed.iter().map(|&(pos, _)| pos + 1).max().unwrap_or(0);
+    let mut letters = vec![None; len];
+    for &(pos, c) in fixed {
+      letters[pos] = Some(into_index(c)?);
+    }
+
+    let mut words = vec![];
+    let tiles = Tiles::parse(rack)?;
+    self.through_from(
+      tiles,
+      &letters,
+      &lengths,
+      false,
+      &mut String::new(),
+      &mut words,
+    );
+    Some(words)
+  }
+
+  /// All the words that use every tile in a rack exactly once, in order.
+  ///
+  /// Racks are written as lowercase letters, with `?` for a blank tile.
+  /// Returns `None` if the rack contains any other characters.
+  pub fn anagrams(&self, rack: &str) -> Option<Vec<String>> {
+    self.anagrams_with(rack, &AnagramOptions::default())
+  }
+
+  /// All the words that use every counted letter exactly once, in order.
+  pub fn anagrams_of(&self, counts: &LetterCounts) -> Vec<String> {
+    let mut words = vec![];
+    let options = AnagramOptions::default();
+    self.anagrams_from(
+      Tiles::counted(counts),
+      &options,
+      &mut String::new(),
+      &mut words,
+    );
+    words
+  }
+
+  /// All the words that can be spelt using some of the counted letters,
+  /// e.g. from the letters of a sentence, in order.
+  pub fn words_from(&self, counts: &LetterCounts) -> Vec<String> {
+    let mut words = vec![];
+    let options = AnagramOptions {
+      partial: true,
+      ..Default::default()
+    };
+    self.anagrams_from(
+      Tiles::counted(counts),
+      &options,
+      &mut String::new(),
+      &mut words,
+    );
+    words
+  }
+}
@@ -0,0 +1,153 @@
+use super::{front_coding, metadata::read_metadata, trailing_bytes, Trie};
+use crate::{alphabet::into_index_unchecked, letter::Letter};
+use std::{io, path::Path, sync::Arc};
+use tokio::{fs, task::yield_now};
+
+/// How many nodes or words to parse before yielding back to the runtime.
+const YIELD_EVERY: usize = 4096;
+
+fn unsupported(action: &str) -> io::Error {
+  io::Error::new(
+    io::ErrorKind::InvalidData,
+    format!("File type not supported, please {action} either a .tre or .txt file."),
+  )
+}
+
+/// Parses the depth-first node masks of a `.tre` file, failing if there's
+/// anything after the last node, the same as [`Trie::load`].
+///
+/// This is iterative rather than recursive, so that parsing can yield.
+async fn parse_trie(bytes: &[u8]) -> io::Result<Trie> {
+  let mut bytes = bytes;
+  read_metadata(&mut bytes)?;
+  let mut next_node = |c: Option<char>| {
+    let (chunk, rest) = bytes.split_first_chunk().ok_or(io::ErrorKind::UnexpectedEof)?;
+    bytes = rest;
+    let mask = u32::from_be_bytes(*chunk);
+    let mut node = Trie::empty();
+    node.is_end = mask & (1 << 26) > 0;
+    io::Result::Ok((node, Letter::from_mask(mask), c))
+  };
+
+  // each frame is a node, the children it's still waiting for
+  // and the character it will be stored under in its parent
+  let mut stack = vec![next_node(None)?];
+  let mut parsed = 0;
+  let root = loop {
+    let (_, pending, _) = stack.last_mut().expect("stack won't be empty");
+    if let Some(c) = pending.next() {
+      stack.push(next_node(Some(c))?);
+      parsed += 1;
+      if parsed % YIELD_EVERY == 0 {
+        yield_now().await
+      }
+      continue;
+    }
+
+    let (node, _, c) = stack.pop().expect("stack won't be empty");
+    match (stack.last_mut(), c) {
+      (Some((parent, _, _)), Some(c)) => {
+        parent.children[into_index_unchecked(c)] = Some(Arc::new(node))
+      }
+      _ => break node,
+    }
+  };
+
+  match bytes.len() {
+    0 => Ok(root),
+    count => Err(trailing_bytes(count)),
+  }
+}
+
+impl Trie {
+  /// Loads a trie from a `.tre` or `.txt` file without blocking the runtime.
+  ///
+  /// The file is read asynchronously and parsing periodically yields, so
+  /// other tasks keep running whilst large word lists are loaded.
+  pub async fn load_async(path: &Path) -> io::Result<Trie> {
+    match path.extension().and_then(|s| s.to_str()) {
+      Some("tre") => parse_trie(&fs::read(path).await?).await,
+      Some("txt") => {
+        let mut trie = Trie::empty();
+        let text = fs::read_to_string(path).await?;
+        let lines = text.lines().map(|line| Ok(line.to_owned()));
+        for (i, word) in front_coding::words(lines).enumerate() {
+          trie.add_assign(&word?);
+          if (i + 1) % YIELD_EVERY == 0 {
+            yield_now().await
+          }
+        }
+        Ok(trie)
+      }
+      Some(_) | None => Err(unsupported("load from")),
+    }
+  }
+
+  /// Saves a trie to a `.tre` or `.txt` file without blocking the runtime.
+  pub async fn save_async(&self, path: &Path) -> io::Result<()> {
+    let mut bytes = vec![];
+    match path.extension().and_then(|s| s.to_str()) {
+      Some("tre") => self.save_trie(&mut bytes)?,
+      Some("txt") => self.clone().save_words(&mut bytes)?,
+      Some(_) | None => return Err(unsupported("save to")),
+    }
+    fs::write(path, bytes).await
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use std::future::Future;
+
+  fn block_on<F: Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+      .build()
+      .unwrap()
+      .block_on(future)
+  }
+
+  fn round_trip(name: &str) {
+    let path = crate::temp_path(name);
+    let trie = Trie::str("[bc]a[rt]");
+    block_on(trie.save_async(&path)).unwrap();
+    let loaded = block_on(Trie::load_async(&path)).unwrap();
+    let synced = Trie::file(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(loaded.has_all("[bc]a[rt]"));
+    assert_eq!(loaded.len(), 4);
+    assert_eq!(loaded, synced);
+  }
+
+  #[test]
+  fn tre() {
+    // saving and loading a .tre file should give back the same trie
+    round_trip("scrabble_async_io.tre")
+  }
+
+  #[test]
+  fn txt() {
+    // saving and loading a .txt file should give back the same words
+    round_trip("scrabble_async_io.txt")
+  }
+
+  #[test]
+  fn truncated() {
+    // a .tre file that stops part way through a node should fail
+    assert!(block_on(parse_trie(&[0, 0, 0, 1])).is_err());
+    assert!(block_on(parse_trie(&[0, 0, 0, 1, 0x04, 0, 0])).is_err());
+  }
+
+  #[test]
+  fn trailing() {
+    // anything after the last node should fail, as it does when syncing
+    let path = crate::temp_path("scrabble_async_io_trailing.tre");
+    for extra in [&[0][..], &[0x04, 0, 0, 0]] {
+      let bytes = [&[0x04, 0, 0, 0][..], extra].concat();
+      std::fs::write(&path, bytes).unwrap();
+      assert!(block_on(Trie::load_async(&path)).is_err());
+      assert!(Trie::file(&path).is_err());
+    }
+    std::fs::remove_file(&path).unwrap();
+  }
+}
@@ -0,0 +1,98 @@
+use super::Trie;
+use crate::alphabet::into_index;
+use std::collections::HashMap;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// How many sorted queries are walked together before starting afresh.
+const CHUNK: usize = 1024;
+
+impl Trie {
+  /// Looks up sorted words, reusing the nodes for each prefix
+  /// shared with the previous word rather than starting from the root.
+  fn has_sorted(&self, words: &[(usize, &str)]) -> Vec<bool> {
+    let mut path = vec![self];
+    let mut prev: &[u8] = &[];
+    words
+      .iter()
+      .map(|&(_, word)| {
+        let word = word.as_bytes();
+        let common = prev.iter().zip(word).take_while(|(a, b)| a == b).count();
+        // the path may already be shorter if the previous word wasn't found
+        path.truncate(common + 1);
+        for &b in &word[path.len() - 1..] {
+          let node = path.last().expect("path contains the root");
+          let next = into_index(b as char).and_then(|i| node.children[i].as_deref());
+          match next {
+            Some(next) => path.push(next),
+            None => break,
+          }
+        }
+        prev = word;
+        path.len() == word.len() + 1 && path.last().is_some_and(|node| node.is_end)
+      })
+      .collect()
+  }
+
+  /// Whether each of the words is in the trie, in the order given.
+  ///
+  /// Unlike [`Trie::has`] the words aren't parsed as patterns, so anything
+  /// other than `a-z` never matches. Words are sorted so that lookups can
+  /// share the walk down any common prefix, and with the `rayon` feature
+  /// batches of sorted words are looked up in parallel.
+  pub fn has_many<'a>(&self, words: impl IntoIterator<Item = &'a str>) -> Vec<bool> {
+    let mut sorted: Vec<(usize, &str)> = words.into_iter().enumerate().collect();
+    sorted.sort_unstable_by_key(|&(_, word)| word);
+
+    #[cfg(feature = "rayon")]
+    let found: Vec<bool> = sorted
+      .par_chunks(CHUNK)
+      .flat_map_iter(|chunk| self.has_sorted(chunk))
+      .collect();
+    #[cfg(not(feature = "rayon"))]
+    let found: Vec<bool> = sorted
+      .chunks(CHUNK)
+      .flat_map(|chunk| self.has_sorted(chunk))
+      .collect();
+
+    let mut results = vec![false; sorted.len()];
+    for (&(i, _), found) in sorted.iter().zip(found) {
+      results[i] = found
+    }
+    results
+  }
+
+  /// The anagrams of each of the racks, in the order given.
+  ///
+  /// Racks with the same tiles in a different order are only solved once,
+  /// and with the `rayon` feature distinct racks are solved in parallel.
+  pub fn anagrams_many<'a>(
+    &self,
+    racks: impl IntoIterator<Item = &'a str>,
+  ) -> Vec<Option<Vec<String>>> {
+    let keys: Vec<String> = racks
+      .into_iter()
+      .map(|rack| {
+        let mut tiles: Vec<char> = rack.chars().collect();
+        tiles.sort_unstable();
+        tiles.into_iter().collect()
+      })
+      .collect();
+    let mut distinct: Vec<&str> = keys.iter().map(String::as_str).collect();
+    distinct.sort_unstable();
+    distinct.dedup();
+
+    #[cfg(feature = "rayon")]
+    let solved = distinct.into_par_iter();
+    #[cfg(not(feature = "rayon"))]
+    let solved = distinct.into_iter();
+    let solved: HashMap<&str, Option<Vec<String>>> =
+      solved.map(|rack| (rack, self.anagrams(rack))).collect();
+
+    keys
+      .iter()
+      .map(|key| solved[key.as_str()].clone())
+      .collect()
+  }
+}
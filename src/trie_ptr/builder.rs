@@ -0,0 +1,95 @@
+use super::Trie;
+use std::sync::Arc;
+
+/// Builds a trie from words in alphabetical order, only ever touching the
+/// nodes that differ from the previous word.
+///
+/// The nodes along the path of the last word are kept open on a stack, and
+/// are closed into their parents as soon as a later word branches off before
+/// them. If a word arrives out of order, or isn't a plain word in `a-z`, the
+/// builder falls back to inserting from the root for the rest of the input.
+#[derive(Debug, Clone)]
+pub struct TrieBuilder {
+  path: Vec<Trie>,
+  last: String,
+  unsorted: Option<Trie>,
+}
+
+impl Default for TrieBuilder {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl TrieBuilder {
+  pub fn new() -> Self {
+    TrieBuilder {
+      path: vec![Trie::empty()],
+      last: String::new(),
+      unsorted: None,
+    }
+  }
+
+  /// Builds a trie from words, which are expected to be sorted.
+  pub fn from_sorted<S: AsRef<str>, I: IntoIterator<Item = S>>(iter: I) -> Trie {
+    let mut builder = Self::new();
+    for word in iter {
+      builder.push(word.as_ref())
+    }
+    builder.finish()
+  }
+
+  /// Whether every word so far has been in order.
+  pub fn is_sorted(&self) -> bool {
+    self.unsorted.is_none()
+  }
+
+  /// Closes the nodes below a depth into their parents.
+  fn close_to(&mut self, depth: usize) {
+    while self.path.len() > depth + 1 {
+      let node = self.path.pop().expect("path should be deeper than depth");
+      let i = (self.last.as_bytes()[self.path.len() - 1] - b'a') as usize;
+      let parent = self.path.last_mut().expect("root should stay on the path");
+      parent.children[i] = Some(Arc::new(node))
+    }
+  }
+
+  pub fn push(&mut self, word: &str) {
+    if let Some(trie) = &mut self.unsorted {
+      *trie += word;
+      return;
+    }
+    if word < self.last.as_str() || !word.bytes().all(|b| b.is_ascii_lowercase()) {
+      self.close_to(0);
+      let mut trie = self.path.pop().expect("root should stay on the path");
+      trie += word;
+      self.unsorted = Some(trie);
+      return;
+    }
+
+    let common = word
+      .bytes()
+      .zip(self.last.bytes())
+      .take_while(|(a, b)| a == b)
+      .count();
+    self.close_to(common);
+    for _ in common..word.len() {
+      self.path.push(Trie::empty())
+    }
+    self
+      .path
+      .last_mut()
+      .expect("root should stay on the path")
+      .is_end = true;
+    self.last.clear();
+    self.last.push_str(word);
+  }
+
+  pub fn finish(mut self) -> Trie {
+    if let Some(trie) = self.unsorted {
+      return trie;
+    }
+    self.close_to(0);
+    self.path.pop().expect("root should stay on the path")
+  }
+}
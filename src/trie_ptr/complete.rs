@@ -0,0 +1,77 @@
+use super::Trie;
+use crate::{bag::score, trie::TrieNode, trie_map::TrieMap};
+use std::{cmp::Reverse, collections::VecDeque};
+
+/// The order that completions are returned in by [`Trie::complete`].
+///
+/// Ties are always broken alphabetically.
+#[derive(Debug, Clone, Copy)]
+pub enum Ranking<'a> {
+  /// Shortest words first.
+  Length,
+  /// Highest scoring words first, by the face value of their tiles.
+  Score,
+  /// Highest weighted words first, with unweighted words treated as zero.
+  Weights(&'a TrieMap<f64>),
+}
+
+impl Trie {
+  /// Up to `limit` words starting with a prefix, including the prefix
+  /// itself, in the order given by the ranking.
+  pub fn complete(&self, prefix: &str, limit: usize, ranking: Ranking) -> Vec<String> {
+    let mut words = match ranking {
+      // breadth first already finds shorter words first, so can stop early
+      Ranking::Length => self.shortest(prefix, limit),
+      Ranking::Score => {
+        let mut words = self.completions(prefix);
+        words.sort_by_key(|word| Reverse(score(word)));
+        words
+      }
+      Ranking::Weights(weights) => {
+        let weight = |word: &String| weights.get(word).copied().unwrap_or(0.0);
+        let mut words = self.completions(prefix);
+        words.sort_by(|a, b| weight(b).total_cmp(&weight(a)));
+        words
+      }
+    };
+    words.truncate(limit);
+    words
+  }
+
+  /// Every word starting with a prefix, in alphabetical order.
+  fn completions(&self, prefix: &str) -> Vec<String> {
+    self.node(prefix).map_or(vec![], |node| {
+      let longer = node.strings().map(|end| format!("{prefix}{end}"));
+      node
+        .is_end
+        .then(|| prefix.to_owned())
+        .into_iter()
+        .chain(longer)
+        .collect()
+    })
+  }
+
+  /// The shortest words starting with a prefix, found breadth first.
+  fn shortest(&self, prefix: &str, limit: usize) -> Vec<String> {
+    let mut words = vec![];
+    let mut queue: VecDeque<_> = self
+      .node(prefix)
+      .map(|node| (node, prefix.to_owned()))
+      .into_iter()
+      .collect();
+    while let Some((node, word)) = queue.pop_front() {
+      if words.len() >= limit {
+        break;
+      }
+      for (c, child) in ('a'..='z').zip(&node.children) {
+        if let Some(child) = child {
+          queue.push_back((child.as_ref(), format!("{word}{c}")))
+        }
+      }
+      if node.is_end {
+        words.push(word)
+      }
+    }
+    words
+  }
+}
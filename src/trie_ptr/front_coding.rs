@@ -0,0 +1,136 @@
+/*
+Front coded word lists are sorted word lists where each line only stores
+how much of the previous word it shares, followed by the rest of the word:
+
+#front-coded
+0cat
+3s
+0dog
+
+They're still `.txt` files, so loading detects them by their first line.
+*/
+use super::Trie;
+use std::{
+  fs::File,
+  io::{self, BufWriter, Write},
+  path::Path,
+};
+
+/// The first line of a front coded word list.
+pub(super) const HEADER: &str = "#front-coded";
+
+fn invalid(line: &str) -> io::Error {
+  io::Error::new(
+    io::ErrorKind::InvalidData,
+    format!("`{line}` is not a valid front coded line."),
+  )
+}
+
+/// Expands the lines after the header back into words.
+fn decode<I>(lines: I) -> impl Iterator<Item = io::Result<String>>
+where
+  I: Iterator<Item = io::Result<String>>,
+{
+  lines.scan(String::new(), |prev, line| {
+    Some(line.and_then(|line| {
+      let digits = line
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(line.len());
+      let shared: usize = line[..digits].parse().map_err(|_| invalid(&line))?;
+      // splitting a multi-byte letter would panic rather than give an error
+      if shared > prev.len() || !prev.is_char_boundary(shared) {
+        return Err(invalid(&line));
+      }
+      prev.truncate(shared);
+      prev.push_str(&line[digits..]);
+      Ok(prev.clone())
+    }))
+  })
+}
+
+/// The words in the lines of a `.txt` file, whether or not it's front coded.
+pub(crate) fn words<'a, I>(lines: I) -> Box<dyn Iterator<Item = io::Result<String>> + 'a>
+where
+  I: Iterator<Item = io::Result<String>> + 'a,
+{
+  let mut lines = lines.peekable();
+  if matches!(lines.peek(), Some(Ok(line)) if line == HEADER) {
+    lines.next();
+    Box::new(decode(lines))
+  } else {
+    Box::new(lines)
+  }
+}
+
+impl Trie {
+  fn save_front_coded_words<W: Write>(&self, file: &mut W) -> io::Result<()> {
+    writeln!(file, "{HEADER}")?;
+    let mut prev = String::new();
+    for word in self.clone() {
+      let shared = prev.bytes().zip(word.bytes()).take_while(|(a, b)| a == b);
+      let shared = shared.count();
+      writeln!(file, "{shared}{}", &word[shared..])?;
+      prev = word;
+    }
+    Ok(())
+  }
+
+  /// Saves the words to a front coded `.txt` file.
+  ///
+  /// Each line only holds the part of a word not shared with the word
+  /// before it, which is usually a fraction of the size of a plain list.
+  /// Files are detected and expanded when loading, as with [`Trie::file`].
+  pub fn save_front_coded(&self, path: &Path) -> io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+    self.save_front_coded_words(&mut file)?;
+    file.flush()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn encode(words: &[&str]) -> String {
+    let trie: Trie = words.iter().map(|&w| w.to_owned()).collect();
+    let mut bytes = vec![];
+    trie.save_front_coded_words(&mut bytes).unwrap();
+    String::from_utf8(bytes).unwrap()
+  }
+
+  fn lines(text: &str) -> impl Iterator<Item = io::Result<String>> + '_ {
+    text.lines().map(|line| Ok(line.to_owned()))
+  }
+
+  #[test]
+  fn shared_prefixes() {
+    // each line should only store what differs from the previous word
+    let text = encode(&["cat", "cats", "catch", "dog"]);
+    assert_eq!(text, "#front-coded\n0cat\n3ch\n3s\n0dog\n");
+  }
+
+  #[test]
+  fn round_trip() {
+    // decoding should give back the sorted words
+    let text = encode(&["", "ab", "abc", "b", "ba"]);
+    let decoded: io::Result<Vec<String>> = words(lines(&text)).collect();
+    assert_eq!(decoded.unwrap(), ["", "ab", "abc", "b", "ba"]);
+  }
+
+  #[test]
+  fn plain() {
+    // files without the header should be read as plain word lists
+    let decoded: io::Result<Vec<String>> = words(lines("0cat\n3s")).collect();
+    assert_eq!(decoded.unwrap(), ["0cat", "3s"]);
+  }
+
+  #[test]
+  fn invalid_prefix() {
+    // a line can't share more than the previous word
+    let text = "#front-coded\n0ab\n5c";
+    assert!(words(lines(text)).any(|word| word.is_err()));
+    // nor share part of a letter
+    let text = "#front-coded\n0é\n1x";
+    assert!(words(lines(text)).any(|word| word.is_err()));
+  }
+}
@@ -0,0 +1,90 @@
+use super::Trie;
+use crate::{
+  alphabet::{from_index_unchecked, into_index},
+  letter::Letter,
+  trie::TrieNode,
+};
+use std::fmt::Display;
+
+/// A word along with the letters that hook onto either end of it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Annotated {
+  pub word: String,
+  /// Letters that can be placed before the word to make another word.
+  pub front: Letter,
+  /// Letters that can be placed after the word to make another word.
+  pub back: Letter,
+  /// Whether the word without its first letter is a word.
+  pub inner_front: bool,
+  /// Whether the word without its last letter is a word.
+  pub inner_back: bool,
+}
+
+impl Display for Annotated {
+  /// Displays hooks as Zyzzyva does, with dots marking inner hooks, i.e.
+  /// `ch ·at es` for `at`, hooked by `cat`, `hat`, `ate` and `ats`, when
+  /// `t` is also a word.
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let dot = |inner: bool| if inner { "·" } else { "" };
+    let front: String = self.front.chars().collect();
+    let back: String = self.back.chars().collect();
+    write!(
+      f,
+      "{front} {}{}{} {back}",
+      dot(self.inner_front),
+      self.word,
+      dot(self.inner_back)
+    )
+  }
+}
+
+impl Trie {
+  /// The node at the end of a word, if the trie extends that far.
+  pub(super) fn node(&self, word: &str) -> Option<&Trie> {
+    word
+      .chars()
+      .try_fold(self, |node, c| node.children[into_index(c)?].as_deref())
+  }
+
+  fn ends(&self, word: &str) -> bool {
+    self.node(word).is_some_and(|node| node.is_end)
+  }
+
+  /// The letters that can be placed before a word to make another word.
+  pub fn front_hooks(&self, word: &str) -> Letter {
+    let hooks = self.children.iter().enumerate().filter_map(|(i, child)| {
+      let hooked = child.as_ref()?.ends(word);
+      hooked.then(|| from_index_unchecked(i))
+    });
+    hooks.collect()
+  }
+
+  /// The letters that can be placed after a word to make another word.
+  pub fn back_hooks(&self, word: &str) -> Letter {
+    let Some(node) = self.node(word) else {
+      return Letter::default();
+    };
+    let hooks = node.children.iter().enumerate().filter_map(|(i, child)| {
+      let hooked = child.as_ref()?.is_end;
+      hooked.then(|| from_index_unchecked(i))
+    });
+    hooks.collect()
+  }
+
+  /// Annotates every word with its hooks, in alphabetical order.
+  ///
+  /// Words are annotated as they're reached rather than all up front, so
+  /// this can stream a whole lexicon.
+  pub fn annotated_words(&self) -> impl Iterator<Item = Annotated> + '_ {
+    self.strings().map(|word| {
+      let inner = |word: Option<&str>| word.is_some_and(|word| self.ends(word));
+      Annotated {
+        front: self.front_hooks(&word),
+        back: self.back_hooks(&word),
+        inner_front: inner(word.get(1..).filter(|_| !word.is_empty())),
+        inner_back: inner(word.len().checked_sub(1).map(|end| &word[..end])),
+        word,
+      }
+    })
+  }
+}
@@ -0,0 +1,36 @@
+use super::Trie;
+use std::{collections::BTreeMap, sync::Arc};
+
+impl Trie {
+  /// The words of exactly `n` letters, found without walking any deeper.
+  pub fn words_of_length(&self, n: usize) -> Trie {
+    let mut trie = Trie::empty();
+    let Some(n) = n.checked_sub(1) else {
+      trie.is_end = self.is_end;
+      return trie;
+    };
+
+    for (slot, child) in trie.children.iter_mut().zip(&self.children) {
+      let sub = child.as_ref().map(|child| child.words_of_length(n));
+      *slot = sub.filter(|sub| !sub.is_empty()).map(Arc::new);
+    }
+    trie
+  }
+
+  /// Splits the words into tries by their length, in a single pass.
+  pub fn split_by_length(&self) -> BTreeMap<usize, Trie> {
+    let mut lengths = BTreeMap::new();
+    if self.is_end {
+      lengths.insert(0, Trie::str(""));
+    }
+
+    for (i, child) in self.children.iter().enumerate() {
+      let Some(child) = child else { continue };
+      for (len, sub) in child.split_by_length() {
+        let trie = lengths.entry(len + 1).or_insert_with(Trie::empty);
+        trie.children[i] = Some(Arc::new(sub));
+      }
+    }
+    lengths
+  }
+}
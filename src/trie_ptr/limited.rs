@@ -0,0 +1,137 @@
+use super::Trie;
+use crate::{
+  alphabet::from_index_unchecked,
+  limits::{LimitExceeded, Limits},
+  set::Set,
+  word::Word,
+};
+use std::{array, sync::Arc};
+
+/// How a pair of children combine in a set operation.
+#[derive(Clone, Copy)]
+enum Op {
+  And,
+  Or,
+  Diff,
+}
+
+/// Counts down the nodes an operation can still visit.
+struct Budget {
+  limits: Limits,
+  nodes: usize,
+}
+
+impl Budget {
+  fn new(limits: &Limits) -> Self {
+    Budget {
+      limits: *limits,
+      nodes: limits.max_nodes,
+    }
+  }
+
+  fn visit(&mut self, depth: usize) -> Result<(), LimitExceeded> {
+    if depth > self.limits.max_depth {
+      return Err(LimitExceeded::Depth(self.limits.max_depth));
+    }
+    self.nodes = (self.nodes.checked_sub(1)).ok_or(LimitExceeded::Nodes(self.limits.max_nodes))?;
+    Ok(())
+  }
+}
+
+fn combine(
+  a: Option<&Arc<Trie>>,
+  b: Option<&Arc<Trie>>,
+  op: Op,
+  depth: usize,
+  budget: &mut Budget,
+) -> Result<Option<Arc<Trie>>, LimitExceeded> {
+  // shared or one sided branches are settled without walking them
+  let (a, b) = match (op, a, b) {
+    (_, None, None) | (Op::And, None, _) | (Op::And, _, None) | (Op::Diff, None, _) => {
+      return Ok(None)
+    }
+    (Op::Or | Op::Diff, Some(a), None) | (Op::Or, None, Some(a)) => return Ok(Some(a.clone())),
+    (Op::And | Op::Or, Some(a), Some(b)) if Arc::ptr_eq(a, b) => return Ok(Some(a.clone())),
+    (Op::Diff, Some(a), Some(b)) if Arc::ptr_eq(a, b) => return Ok(None),
+    (_, Some(a), Some(b)) => (a, b),
+  };
+
+  let trie = a.combine(b, op, depth, budget)?;
+  Ok((!trie.is_empty()).then(|| Arc::new(trie)))
+}
+
+impl Trie {
+  fn combine(
+    &self,
+    other: &Trie,
+    op: Op,
+    depth: usize,
+    budget: &mut Budget,
+  ) -> Result<Trie, LimitExceeded> {
+    budget.visit(depth)?;
+    let mut children: [Option<Arc<Trie>>; 26] = array::from_fn(|_| None);
+    for (i, child) in children.iter_mut().enumerate() {
+      let (a, b) = (self.children[i].as_ref(), other.children[i].as_ref());
+      *child = combine(a, b, op, depth + 1, budget)?;
+    }
+    let is_end = match op {
+      Op::And => self.is_end & other.is_end,
+      Op::Or => self.is_end | other.is_end,
+      Op::Diff => self.is_end & !other.is_end,
+    };
+    Ok(Trie { is_end, children })
+  }
+
+  /// Builds a trie from a pattern, provided it isn't too long and doesn't
+  /// match too many words.
+  pub fn word_within(word: Word, limits: &Limits) -> Result<Self, LimitExceeded> {
+    if word.len() > limits.max_depth {
+      return Err(LimitExceeded::Depth(limits.max_depth));
+    }
+    let matches = word
+      .iter()
+      .try_fold(1usize, |count, letter| count.checked_mul(letter.len()));
+    if matches.is_none_or(|count| count > limits.max_results) {
+      return Err(LimitExceeded::Results(limits.max_results));
+    }
+    Ok(Self::word(word))
+  }
+
+  /// The intersection of two tries, see [`Trie::word_within`] for limits.
+  pub fn and_within(&self, other: &Trie, limits: &Limits) -> Result<Self, LimitExceeded> {
+    self.combine(other, Op::And, 0, &mut Budget::new(limits))
+  }
+
+  /// The union of two tries, see [`Trie::word_within`] for limits.
+  pub fn or_within(&self, other: &Trie, limits: &Limits) -> Result<Self, LimitExceeded> {
+    self.combine(other, Op::Or, 0, &mut Budget::new(limits))
+  }
+
+  /// The words in this trie but not the other, see [`Trie::word_within`].
+  pub fn diff_within(&self, other: &Trie, limits: &Limits) -> Result<Self, LimitExceeded> {
+    self.combine(other, Op::Diff, 0, &mut Budget::new(limits))
+  }
+
+  /// Lists the words in the trie, unless there are too many of them.
+  pub fn strings_within(&self, limits: &Limits) -> Result<Vec<String>, LimitExceeded> {
+    let mut words = vec![];
+    let mut stack = vec![(self, String::new())];
+    while let Some((node, word)) = stack.pop() {
+      if word.len() > limits.max_depth {
+        return Err(LimitExceeded::Depth(limits.max_depth));
+      }
+      if node.is_end {
+        if words.len() >= limits.max_results {
+          return Err(LimitExceeded::Results(limits.max_results));
+        }
+        words.push(word.clone())
+      }
+      for (i, child) in node.children.iter().enumerate().rev() {
+        if let Some(child) = child {
+          stack.push((child, format!("{word}{}", from_index_unchecked(i))))
+        }
+      }
+    }
+    Ok(words)
+  }
+}
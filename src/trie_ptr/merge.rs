@@ -0,0 +1,81 @@
+use super::Trie;
+use std::{
+  fmt::Display,
+  io,
+  path::{Path, PathBuf},
+};
+
+/// The words each source added to a merged dictionary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceReport {
+  pub path: PathBuf,
+  /// The number of words in the source.
+  pub words: usize,
+  /// The number of those words already found in earlier sources.
+  pub overlap: usize,
+}
+
+impl SourceReport {
+  /// The number of words only this source added so far.
+  pub fn added(&self) -> usize {
+    self.words - self.overlap
+  }
+}
+
+/// A summary of merging several word lists into one dictionary.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MergeReport {
+  /// Each source, in the order they were merged.
+  pub sources: Vec<SourceReport>,
+  /// The number of distinct words across every source.
+  pub total: usize,
+}
+
+impl MergeReport {
+  /// The number of words dropped as duplicates of earlier sources.
+  pub fn duplicates(&self) -> usize {
+    self.sources.iter().map(|source| source.overlap).sum()
+  }
+}
+
+impl Display for MergeReport {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    for source in &self.sources {
+      writeln!(
+        f,
+        "{}: {} words, {} new, {} already seen",
+        source.path.display(),
+        source.words,
+        source.added(),
+        source.overlap
+      )?;
+    }
+    write!(
+      f,
+      "{} words in total, {} duplicates dropped",
+      self.total,
+      self.duplicates()
+    )
+  }
+}
+
+impl Trie {
+  /// Loads and unions several `.tre` or `.txt` files, reporting how many
+  /// words each contributed and how many it shared with earlier files.
+  pub fn load_many<P: AsRef<Path>>(paths: &[P]) -> io::Result<(Trie, MergeReport)> {
+    let mut merged = Trie::empty();
+    let mut report = MergeReport::default();
+    for path in paths {
+      let trie = Trie::file(path.as_ref())?;
+      let overlap = (&merged & trie.clone()).len();
+      report.sources.push(SourceReport {
+        path: path.as_ref().to_owned(),
+        words: trie.len(),
+        overlap,
+      });
+      merged |= trie;
+    }
+    report.total = merged.len();
+    Ok((merged, report))
+  }
+}
@@ -0,0 +1,92 @@
+use super::Trie;
+use std::{
+  fs::File,
+  io::{self, BufRead, BufReader, Write},
+  path::Path,
+};
+
+/// The bytes starting a `.tre` file with a metadata block.
+///
+/// Node headers never set the bits above the end flag, which these bytes
+/// do, so files without metadata are still read as before.
+const MAGIC: [u8; 4] = *b"TRE\x01";
+
+/// Details identifying a saved lexicon, such as which edition it is.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Metadata {
+  pub name: Option<String>,
+  pub version: Option<String>,
+  pub license: Option<String>,
+  /// The SHA-256 of the word list the lexicon was built from, in hex.
+  pub source_sha256: Option<String>,
+}
+
+impl Metadata {
+  fn fields(&mut self) -> [(&'static str, &mut Option<String>); 4] {
+    [
+      ("name", &mut self.name),
+      ("version", &mut self.version),
+      ("license", &mut self.license),
+      ("source_sha256", &mut self.source_sha256),
+    ]
+  }
+
+  /// Writes each field set as a `key: value` line.
+  fn encode(&self) -> String {
+    let mut copy = self.clone();
+    let fields = copy.fields().into_iter();
+    let lines = fields.filter_map(|(key, value)| {
+      let value = value.as_ref()?.replace('\n', " ");
+      Some(format!("{key}: {value}\n"))
+    });
+    lines.collect()
+  }
+
+  /// Reads `key: value` lines, skipping any keys it doesn't know.
+  fn decode(text: &str) -> Self {
+    let mut metadata = Metadata::default();
+    for (key, value) in text.lines().filter_map(|line| line.split_once(": ")) {
+      for (field, slot) in metadata.fields() {
+        if field == key {
+          *slot = Some(value.to_owned())
+        }
+      }
+    }
+    metadata
+  }
+}
+
+/// Reads the metadata block from the start of a `.tre` file if there is
+/// one, leaving the reader at the first node either way.
+pub(crate) fn read_metadata<R: BufRead>(reader: &mut R) -> io::Result<Option<Metadata>> {
+  if !reader.fill_buf()?.starts_with(&MAGIC) {
+    return Ok(None);
+  }
+  reader.consume(MAGIC.len());
+
+  let mut len = [0; 4];
+  reader.read_exact(&mut len)?;
+  let mut text = vec![0; u32::from_be_bytes(len) as usize];
+  reader.read_exact(&mut text)?;
+  let text =
+    String::from_utf8(text).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+  Ok(Some(Metadata::decode(&text)))
+}
+
+impl Trie {
+  /// Reads just the metadata of a `.tre` file, without loading the trie.
+  pub fn metadata(path: &Path) -> io::Result<Option<Metadata>> {
+    read_metadata(&mut BufReader::new(File::open(path)?))
+  }
+
+  /// Saves the trie to a `.tre` file, preceded by a metadata block.
+  pub fn save_with_metadata(&self, path: &Path, metadata: &Metadata) -> io::Result<()> {
+    let text = metadata.encode();
+    let mut file = io::BufWriter::new(File::create(path)?);
+    file.write_all(&MAGIC)?;
+    file.write_all(&(text.len() as u32).to_be_bytes())?;
+    file.write_all(text.as_bytes())?;
+    self.save_trie(&mut file)?;
+    file.flush()
+  }
+}
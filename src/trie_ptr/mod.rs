@@ -1,52 +1,101 @@
+mod anagram;
+#[cfg(feature = "tokio")]
+mod async_io;
+mod batch;
+mod builder;
+mod complete;
+pub(crate) mod front_coding;
+mod hooks;
+mod length;
+mod limited;
+mod merge;
+pub(crate) mod metadata;
+mod node;
+mod partial;
+#[cfg(feature = "rand")]
 pub mod random;
+mod segment;
 pub mod test;
-
-use crate::{letter::from_index, word::Word};
-use std::{array, io, path::Path};
-
-#[derive(Debug, Clone, PartialEq, Eq)]
+mod verify;
+
+pub use anagram::AnagramOptions;
+pub use builder::TrieBuilder;
+pub use complete::Ranking;
+pub use hooks::Annotated;
+pub use merge::{MergeReport, SourceReport};
+pub use metadata::Metadata;
+pub use verify::{Issue, VerifyReport};
+
+use crate::{
+  alphabet::{from_index_unchecked, into_index_unchecked},
+  letter::Letter,
+  trie::{branches, DisplayOptions, RenderStyle, TrieNode},
+  word::{ParseError, Word},
+};
+use std::{array, io, path::Path, sync::Arc};
+
+/// A trie where each node owns an array of children, one for each letter.
+///
+/// Children are reference counted and copied on write, so cloning a trie
+/// only copies its root and clones can be kept as cheap snapshots.
+///
+/// Tries compare equal when they hold the same words, even if one of them
+/// still has branches without any words in them.
+#[derive(Debug, Clone, Eq)]
 pub struct Trie {
   is_end: bool,
-  children: [Option<Box<Trie>>; 26],
+  children: [Option<Arc<Trie>>; 26],
+}
+
+impl PartialEq for Trie {
+  fn eq(&self, other: &Self) -> bool {
+    self.is_end == other.is_end
+      && self
+        .children
+        .iter()
+        .zip(&other.children)
+        .all(|pair| match pair {
+          (Some(a), Some(b)) => Arc::ptr_eq(a, b) || a == b,
+          (Some(child), None) | (None, Some(child)) => child.is_empty(),
+          (None, None) => true,
+        })
+  }
+}
+
+impl Drop for Trie {
+  /// Drops the nodes only this trie holds one at a time, as dropping them
+  /// recursively would overflow the stack on long enough words.
+  fn drop(&mut self) {
+    let mut owned = vec![];
+    let take_children = |node: &mut Trie, owned: &mut Vec<Trie>| {
+      let children = node.children.iter_mut().filter_map(Option::take);
+      owned.extend(children.filter_map(Arc::into_inner));
+    };
+    take_children(self, &mut owned);
+    while let Some(mut node) = owned.pop() {
+      take_children(&mut node, &mut owned)
+    }
+  }
 }
 
 /*
 @note a trie should have some debugging tools:
 > Display
 */
-use std::fmt::Display;
+use crate::{memory::MemoryReport, mph::Mph};
+use std::{collections::HashSet, fmt::Display, mem};
 
 impl Trie {
   fn branches(&self) -> String {
-    let mut child_strs: Vec<String> = self
-      .children
-      .iter()
-      .enumerate()
-      .flat_map(|(i, c)| {
-        let trie = c.as_ref()?;
-
-        let letter = if trie.is_end {
-          format!("╸{}", from_index(i))
-        } else {
-          format!("╴{}", from_index(i))
-        };
-
-        if trie.is_leaf() {
-          Some(format!("├─{}\n", letter))
-        } else {
-          let s = format!("├┬{}\n{}", letter, trie.branches());
-          Some(s.replace('\n', "\n│") + "\n")
-        }
-      })
-      .collect();
-
-    // remove prefix characters from the last branch
-    if let Some(last) = child_strs.last_mut() {
-      *last = last.replacen('├', "└", 1);
-      *last = last.replace("\n│", "\n ");
-    }
+    self.display_options(DisplayOptions {
+      style: RenderStyle::from_env(),
+      ..Default::default()
+    })
+  }
 
-    String::from_iter(child_strs).trim_end().to_owned()
+  /// Draws a view of the trie, truncated by the given options.
+  pub fn display_options(&self, options: DisplayOptions) -> String {
+    branches(self, &options)
   }
 
   pub fn len(&self) -> usize {
@@ -74,6 +123,56 @@ impl Trie {
     res.insert(0, self.is_end.into());
     res
   }
+
+  /// Estimates the memory used by this trie, see [`MemoryReport`].
+  ///
+  /// Nodes shared with snapshots or between branches are only counted once.
+  pub fn memory_report(&self) -> MemoryReport {
+    let mut report = MemoryReport::default();
+    let mut seen = HashSet::new();
+    let mut level = vec![self];
+    while !level.is_empty() {
+      report.depths.push(level.len());
+
+      let mut next = vec![];
+      for child in level.iter().flat_map(|trie| trie.children.iter().flatten()) {
+        if seen.insert(Arc::as_ptr(child)) {
+          next.push(child.as_ref())
+        }
+      }
+      level = next;
+    }
+
+    report.tree_nodes = self.tree_nodes();
+    report.nodes = seen.len() + 1;
+    // every node apart from the root is allocated alongside its counts
+    report.heap_bytes = seen.len() * (mem::size_of::<Trie>() + 2 * mem::size_of::<usize>());
+    report
+  }
+
+  /// The number of nodes this trie would take up without any sharing.
+  fn tree_nodes(&self) -> usize {
+    self
+      .children
+      .iter()
+      .flatten()
+      .fold(1, |count, child| count.saturating_add(child.tree_nodes()))
+  }
+
+  /// Builds a minimal perfect hash of the words in this trie.
+  ///
+  /// This maps each word to a dense index and back, see [`Mph`].
+  pub fn build_mph(&self) -> Mph {
+    Mph::new(self)
+  }
+
+  /// A cheap copy of this trie that shares all of its nodes.
+  ///
+  /// Nodes are only copied when either trie modifies them, so a snapshot
+  /// keeps answering queries for the old words whilst the original changes.
+  pub fn snapshot(&self) -> Trie {
+    self.clone()
+  }
 }
 
 impl Display for Trie {
@@ -101,7 +200,7 @@ impl Trie {
         children: array::from_fn(|_| None),
       },
       |(letter, next)| {
-        let trie = Box::new(Self::word(next));
+        let trie = Arc::new(Self::word(next));
         Trie {
           is_end: false,
           children: array::from_fn(|i| letter.has_idx(i).then(|| trie.clone())),
@@ -110,8 +209,10 @@ impl Trie {
     )
   }
 
+  /// Creates a trie from a pattern, ignoring everything after the first
+  /// invalid letter, see [`Word::parse_lenient`].
   pub fn str(word: &str) -> Self {
-    Self::word(word.parse().unwrap())
+    Self::word(Word::parse_lenient(word))
   }
 
   pub fn all(len: usize) -> Self {
@@ -129,12 +230,23 @@ impl Trie {
       & self
         .children
         .iter()
-        .all(|c| c.as_ref().map_or(true, |n| n.is_empty()))
+        .all(|c| c.as_ref().is_none_or(|n| n.is_empty()))
   }
 
   pub fn is_leaf(&self) -> bool {
     self.children.iter().all(|c| c.is_none())
   }
+
+  /// The letters this node has children for.
+  pub fn child_letter(&self) -> Letter {
+    self
+      .children
+      .iter()
+      .enumerate()
+      .filter(|(_, c)| c.is_some())
+      .map(|(i, _)| from_index_unchecked(i))
+      .collect()
+  }
 }
 
 impl From<()> for Trie {
@@ -170,20 +282,58 @@ impl Iterator for Trie {
     }
 
     self.children.iter_mut().enumerate().find_map(|(i, child)| {
-      child
-        .as_mut()
-        .and_then(|trie| trie.next().map(|s| format!("{}{}", from_index(i), s)))
+      child.as_mut().and_then(|trie| {
+        Arc::make_mut(trie)
+          .next()
+          .map(|s| format!("{}{}", from_index_unchecked(i), s))
+      })
     })
   }
 }
 
 impl FromIterator<String> for Trie {
+  /// Collects words into a trie, which is fastest for sorted words.
   fn from_iter<T: IntoIterator<Item = String>>(iter: T) -> Self {
+    TrieBuilder::from_sorted(iter)
+  }
+}
+
+impl<'a> FromIterator<&'a str> for Trie {
+  /// Collects words into a trie, which is fastest for sorted words.
+  fn from_iter<T: IntoIterator<Item = &'a str>>(iter: T) -> Self {
+    TrieBuilder::from_sorted(iter)
+  }
+}
+
+impl FromIterator<Word> for Trie {
+  fn from_iter<T: IntoIterator<Item = Word>>(iter: T) -> Self {
     let mut trie = Trie::empty();
+    trie.extend(iter);
+    trie
+  }
+}
+
+impl Extend<String> for Trie {
+  fn extend<T: IntoIterator<Item = String>>(&mut self, iter: T) {
     for s in iter {
-      trie += &s
+      *self += &s
+    }
+  }
+}
+
+impl<'a> Extend<&'a str> for Trie {
+  fn extend<T: IntoIterator<Item = &'a str>>(&mut self, iter: T) {
+    for s in iter {
+      *self += s
+    }
+  }
+}
+
+impl Extend<Word> for Trie {
+  fn extend<T: IntoIterator<Item = Word>>(&mut self, iter: T) {
+    for word in iter {
+      self.or_assign(&Trie::word(word))
     }
-    trie
   }
 }
 
@@ -195,7 +345,7 @@ impl Trie {
   pub fn prune(&mut self) {
     for child in self.children.iter_mut() {
       if let Some(node) = child {
-        node.prune();
+        Arc::make_mut(node).prune();
         if node.is_empty() {
           *child = None
         }
@@ -219,36 +369,73 @@ use std::ops::{
 };
 
 impl Trie {
-  fn has_word(&self, word: Word) -> bool {
-    match word.split() {
+  fn matches_from(&self, word: &Word, depth: usize) -> bool {
+    match word.get(depth) {
       None => self.is_end,
-      Some((letter, word)) => self.children.iter().enumerate().any(|(i, child)| {
-        letter.has_idx_unchecked(i)
-          && child
-            .as_ref()
-            .map_or(false, |trie| trie.has_word(word.clone()))
+      Some(letter) => letter.indices().any(|i| {
+        self.children[i]
+          .as_ref()
+          .is_some_and(|trie| trie.matches_from(word, depth + 1))
       }),
     }
   }
 
+  /// Whether any word matching the pattern is in the trie.
+  pub fn matches_pattern(&self, pattern: &Word) -> bool {
+    self.matches_from(pattern, 0)
+  }
+
+  /// Whether a single word is in the trie, or an error if it contains
+  /// anything other than the letters `a-z`.
+  ///
+  /// Unlike [`Trie::has`], groups like `[ab]` are rejected rather than
+  /// checked as patterns.
+  pub fn contains_word(&self, word: &str) -> Result<bool, ParseError> {
+    if let Some((offset, c)) = word.char_indices().find(|(_, c)| !c.is_ascii_lowercase()) {
+      return Err(ParseError {
+        input: word.to_owned(),
+        offset,
+        expected: "a letter in a-z",
+        found: Some(c),
+      });
+    }
+    Ok(self.contains_str(word))
+  }
+
+  /// Whether a single word is in the trie, walking its bytes directly so
+  /// that lookups never allocate.
+  ///
+  /// Anything outside of `a-z` is never in the trie.
+  pub fn contains_str(&self, s: &str) -> bool {
+    let node = s.bytes().try_fold(self, |node, b| {
+      let i = b.wrapping_sub(b'a') as usize;
+      node.children.get(i)?.as_deref()
+    });
+    node.is_some_and(|node| node.is_end)
+  }
+
+  /// Whether any word matching a pattern is in the trie, so plain words
+  /// are looked up as you'd expect.
+  ///
+  /// Patterns are accepted silently, see [`Trie::contains_word`] for a
+  /// lookup that only accepts words.
   pub fn has(&self, word: &str) -> bool {
-    self.has_word(word.parse().unwrap())
+    word.parse().is_ok_and(|word| self.matches_pattern(&word))
   }
 
   fn has_all_word(&self, word: Word) -> bool {
     match word.split() {
       None => self.is_end,
-      Some((letter, word)) => self.children.iter().enumerate().all(|(i, child)| {
-        letter.has_idx_unchecked(i)
-          && child
-            .as_ref()
-            .map_or(false, |trie| trie.has_all_word(word.clone()))
+      Some((letter, word)) => letter.indices().all(|i| {
+        self.children[i]
+          .as_ref()
+          .is_some_and(|trie| trie.has_all_word(word.clone()))
       }),
     }
   }
 
   pub fn has_all(&self, word: &str) -> bool {
-    self.has_all_word(word.parse().unwrap())
+    word.parse().is_ok_and(|word| self.has_all_word(word))
   }
 
   fn add_assign(&mut self, word: &str) {
@@ -265,7 +452,7 @@ impl Trie {
     for (selfc, triec) in self.children.iter_mut().zip(trie.children.iter()) {
       if let Some(trien) = triec {
         if let Some(selfn) = selfc {
-          selfn.or_assign(trien)
+          Arc::make_mut(selfn).or_assign(trien)
         } else {
           *selfc = Some(trien.clone())
         }
@@ -279,7 +466,7 @@ impl Trie {
     for (selfc, triec) in self.children.iter_mut().zip(trie.children.iter()) {
       if let Some(trien) = triec {
         if let Some(selfn) = selfc {
-          selfn.and_assign(trien)
+          Arc::make_mut(selfn).and_assign(trien)
         }
       } else {
         *selfc = None
@@ -293,7 +480,11 @@ impl Trie {
     for (selfc, triec) in self.children.iter_mut().zip(trie.children.iter()) {
       if let Some(trien) = triec {
         if let Some(selfn) = selfc {
-          selfn.diff_assign(trien)
+          Arc::make_mut(selfn).diff_assign(trien);
+          // don't leave behind branches that no longer lead to a word
+          if selfn.is_empty() {
+            *selfc = None
+          }
         }
       }
     }
@@ -384,36 +575,61 @@ use std::fs::File;
 use std::io::{BufRead, BufReader, Read, Write};
 use std::ops::{Shl, Shr};
 
-impl Trie {
-  fn load_trie(&mut self, file: &mut File) -> io::Result<()> {
-    let mut buf = [0; 4];
-    file.read_exact(&mut buf)?;
-    let node = u32::from_be_bytes(buf);
-    if node & (1 << 26) > 0 {
-      self.is_end = true
+/// The error for bytes left over after the last node of a `.tre` file.
+pub(super) fn trailing_bytes(count: usize) -> io::Error {
+  io::Error::new(
+    io::ErrorKind::InvalidData,
+    format!("{count} unexpected bytes after the last node."),
+  )
+}
+
+pub(super) fn read_header<R: Read>(reader: &mut R) -> io::Result<u32> {
+  let mut buf = [0; 4];
+  reader.read_exact(&mut buf)?;
+  Ok(u32::from_be_bytes(buf))
+}
+
+/// Reads a node's descendants, given its header.
+///
+/// Nodes still waiting on children are kept on a stack rather than
+/// recursing, so that a file with long words can't overflow the stack.
+pub(super) fn read_node<R: Read>(header: u32, reader: &mut R) -> io::Result<Trie> {
+  let new_node = |header: u32| Trie {
+    is_end: header & (1 << 26) > 0,
+    children: array::from_fn(|_| None),
+  };
+
+  // each frame is a node, the children it's still waiting for
+  // and the character it will be stored under in its parent
+  let mut stack = vec![(new_node(header), Letter::from_mask(header), None)];
+  loop {
+    let (_, pending, _) = stack.last_mut().expect("stack won't be empty");
+    if let Some(c) = pending.next() {
+      let header = read_header(reader)?;
+      stack.push((new_node(header), Letter::from_mask(header), Some(c)));
+      continue;
     }
 
-    for (i, c) in self.children.iter_mut().enumerate() {
-      if node & (1 << i) > 0 {
-        if c.is_none() {
-          *c = Some(Box::new(Self::empty()))
-        }
-        let trie = c.as_mut().unwrap();
-        trie.load_trie(file)?;
-        *c = Some(trie.to_owned())
+    let (node, _, c) = stack.pop().expect("stack won't be empty");
+    match (stack.last_mut(), c) {
+      (Some((parent, _, _)), Some(c)) => {
+        parent.children[into_index_unchecked(c)] = Some(Arc::new(node))
       }
+      _ => return Ok(node),
     }
+  }
+}
 
+impl Trie {
+  fn load_trie<R: Read>(&mut self, file: &mut R) -> io::Result<()> {
+    let loaded = read_node(read_header(file)?, file)?;
+    self.or_assign(&loaded);
     Ok(())
   }
 
-  fn save_trie(&self, file: &mut File) -> io::Result<()> {
+  fn save_trie<W: Write>(&self, file: &mut W) -> io::Result<()> {
     let mut node: u32 = if self.is_end { 1 << 26 } else { 0 };
-    for (i, c) in self.children.iter().enumerate() {
-      if c.is_some() {
-        node |= 1 << i
-      }
-    }
+    node |= self.child_mask();
 
     file.write_all(node.to_be_bytes().as_ref())?;
 
@@ -427,26 +643,37 @@ impl Trie {
   }
 
   fn load_words(&mut self, file: &mut File) -> io::Result<()> {
-    for line in BufReader::new(file).lines() {
-      self.add_assign(&line?);
+    for word in front_coding::words(BufReader::new(file).lines()) {
+      self.add_assign(&word?);
     }
 
     Ok(())
   }
 
-  fn save_words(self, file: &mut File) -> io::Result<()> {
-    for word in self {
+  fn save_words<W: Write>(&self, file: &mut W) -> io::Result<()> {
+    for word in self.clone() {
       writeln!(file, "{}", word)?
     }
 
     Ok(())
   }
 
-  fn load(&mut self, path: &Path) -> io::Result<()> {
+  /// Adds the words in a `.tre` or `.txt` file to the trie, picking the
+  /// format from the file extension.
+  pub fn load(&mut self, path: &Path) -> io::Result<()> {
     let mut file = File::open(path)?;
     let ext = path.extension().and_then(|s| s.to_str());
     match ext {
-      Some("tre") => self.load_trie(&mut file),
+      Some("tre") => {
+        let mut reader = BufReader::new(file);
+        metadata::read_metadata(&mut reader)?;
+        self.load_trie(&mut reader)?;
+        let mut rest = vec![];
+        match reader.read_to_end(&mut rest)? {
+          0 => Ok(()),
+          count => Err(trailing_bytes(count)),
+        }
+      }
       Some("txt") => self.load_words(&mut file),
       Some(_) | None => Err(io::Error::new(
         io::ErrorKind::InvalidData,
@@ -455,7 +682,9 @@ impl Trie {
     }
   }
 
-  fn save(self, path: &Path) -> io::Result<()> {
+  /// Saves the trie to a `.tre` or `.txt` file, picking the format from
+  /// the file extension.
+  pub fn save(&self, path: &Path) -> io::Result<()> {
     let mut file = File::create(path)?;
     let ext = path.extension().and_then(|s| s.to_str());
     match ext {
@@ -467,8 +696,33 @@ impl Trie {
       )),
     }
   }
+
+  /// The trie in the `.tre` format, without a metadata block.
+  pub fn to_bytes(&self) -> Vec<u8> {
+    let mut bytes = vec![];
+    self
+      .save_trie(&mut bytes)
+      .expect("writing to a Vec shouldn't fail");
+    bytes
+  }
+
+  /// Reads a trie from bytes in the `.tre` format, skipping any metadata
+  /// block and failing if there's anything after the last node.
+  pub fn from_bytes(mut bytes: &[u8]) -> io::Result<Self> {
+    metadata::read_metadata(&mut bytes)?;
+    let mut trie = Self::empty();
+    trie.load_trie(&mut bytes)?;
+    if !bytes.is_empty() {
+      return Err(trailing_bytes(bytes.len()));
+    }
+    Ok(trie)
+  }
 }
 
+/// Deprecated: prefer [`Trie::load`], or [`Trie::file`] for a new trie.
+///
+/// Trait impls can't carry `#[deprecated]`, so this is kept only for
+/// existing callers and will be removed in a future release.
 impl Shl<&Path> for &Trie {
   type Output = io::Result<Trie>;
   fn shl(self, rhs: &Path) -> Self::Output {
@@ -478,10 +732,14 @@ impl Shl<&Path> for &Trie {
   }
 }
 
+/// Deprecated: prefer [`Trie::save`].
+///
+/// Trait impls can't carry `#[deprecated]`, so this is kept only for
+/// existing callers and will be removed in a future release.
 impl Shr<&Path> for Trie {
   type Output = io::Result<Trie>;
   fn shr(self, rhs: &Path) -> Self::Output {
-    self.clone().save(rhs)?;
+    self.save(rhs)?;
     Ok(self)
   }
 }
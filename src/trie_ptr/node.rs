@@ -0,0 +1,81 @@
+use super::Trie;
+use crate::{
+  alphabet::{from_index_unchecked, into_index},
+  trie::TrieNode,
+};
+use std::{array, borrow::Cow, sync::Arc};
+
+impl Trie {
+  /// The words under this node that match a predicate, borrowing the node
+  /// back if every word matches so that untouched branches stay shared.
+  fn filter_node<F: FnMut(&str) -> bool>(&self, prefix: &mut String, f: &mut F) -> Cow<'_, Trie> {
+    let is_end = self.is_end && f(prefix);
+    let mut changed = is_end != self.is_end;
+    let mut children: [Option<Cow<Trie>>; 26] = array::from_fn(|i| {
+      let child = self.children[i].as_ref()?;
+      prefix.push(from_index_unchecked(i));
+      let filtered = child.filter_node(prefix, f);
+      prefix.pop();
+      changed |= matches!(filtered, Cow::Owned(_));
+      Some(filtered)
+    });
+
+    if !changed {
+      return Cow::Borrowed(self);
+    }
+    Cow::Owned(Trie {
+      is_end,
+      children: array::from_fn(|i| match children[i].take()? {
+        Cow::Borrowed(_) => self.children[i].clone(),
+        // children are already pruned, so only leaves can be empty
+        Cow::Owned(child) => (child.is_end || !child.is_leaf()).then(|| Arc::new(child)),
+      }),
+    })
+  }
+}
+
+impl TrieNode for Trie {
+  fn empty() -> Self {
+    Trie::empty()
+  }
+
+  fn is_end(&self) -> bool {
+    self.is_end
+  }
+  fn set_end(&mut self, end: bool) {
+    self.is_end = end
+  }
+  fn is_leaf(&self) -> bool {
+    Trie::is_leaf(self)
+  }
+
+  fn get_child(&self, c: char) -> Option<&Self> {
+    self.children[into_index(c)?].as_deref()
+  }
+  fn get_mut_child(&mut self, c: char) -> Option<&mut Self> {
+    self.children[into_index(c)?].as_mut().map(Arc::make_mut)
+  }
+  fn set_child(&mut self, c: char, other: Option<Self>) {
+    if let Some(i) = into_index(c) {
+      self.children[i] = other.map(Arc::new)
+    }
+  }
+
+  // the trie's own versions share untouched branches instead of cloning
+  fn filtered<F: FnMut(&str) -> bool>(&self, mut f: F) -> Self {
+    self.filter_node(&mut String::new(), &mut f).into_owned()
+  }
+  fn and_assign(&mut self, other: &Self) {
+    Trie::and_assign(self, other)
+  }
+  fn or_assign(&mut self, other: &Self) {
+    Trie::or_assign(self, other)
+  }
+  fn diff_assign(&mut self, other: &Self) {
+    Trie::diff_assign(self, other)
+  }
+
+  fn child_mask(&self) -> u32 {
+    self.child_letter().to_mask()
+  }
+}
@@ -0,0 +1,59 @@
+use super::{metadata::read_metadata, read_header, read_node, Trie};
+use crate::{
+  alphabet::into_index,
+  letter::{bits::MASK, Letter},
+};
+use std::{
+  fs::File,
+  io::{self, BufReader, Read},
+  path::Path,
+  sync::Arc,
+};
+
+/// Reads past a node's descendants without building them.
+fn skip_children<R: Read>(header: u32, reader: &mut R) -> io::Result<()> {
+  let mut pending = (header & MASK).count_ones();
+  while pending > 0 {
+    pending += (read_header(reader)? & MASK).count_ones();
+    pending -= 1;
+  }
+  Ok(())
+}
+
+impl Trie {
+  /// Loads only the words starting with a prefix from a `.tre` file.
+  ///
+  /// Branches before the prefix are read past without being built, and
+  /// reading stops as soon as the prefix's branch is done. There's no
+  /// index of where branches start though, so the header of every node
+  /// before the prefix is still read, and a prefix late in the alphabet
+  /// reads most of the file.
+  pub fn load_prefix(path: &Path, prefix: &str) -> io::Result<Trie> {
+    let Some(letters) = prefix.chars().map(into_index).collect::<Option<Vec<_>>>() else {
+      return Ok(Trie::empty());
+    };
+
+    let mut reader = BufReader::new(File::open(path)?);
+    read_metadata(&mut reader)?;
+    let mut header = read_header(&mut reader)?;
+    for &i in &letters {
+      let children = Letter::from_mask(header);
+      if !children.has_idx(i) {
+        return Ok(Trie::empty());
+      }
+      for _ in children.indices().take_while(|&j| j < i) {
+        let sibling = read_header(&mut reader)?;
+        skip_children(sibling, &mut reader)?;
+      }
+      header = read_header(&mut reader)?;
+    }
+
+    let branch = read_node(header, &mut reader)?;
+    let trie = letters.iter().rev().fold(branch, |child, &i| {
+      let mut trie = Trie::empty();
+      trie.children[i] = Some(Arc::new(child));
+      trie
+    });
+    Ok(trie)
+  }
+}
@@ -16,7 +16,7 @@ impl Trie {
       is_end: rng.gen(),
       children: array::from_fn(|_| {
         if rng.gen_bool(branch_p) {
-          Some(Box::new(Self::random(rng, branch_p, len - 1)))
+          Some(Arc::new(Self::random(rng, branch_p, len - 1)))
         } else {
           None
         }
@@ -0,0 +1,42 @@
+use super::Trie;
+use crate::alphabet::into_index;
+
+impl Trie {
+  /// The lengths of the words in the trie that start a piece of text.
+  fn prefix_lengths(&self, text: &[u8]) -> Vec<usize> {
+    let mut lengths = vec![];
+    let mut node = self;
+    for (i, &b) in text.iter().enumerate() {
+      let Some(child) = into_index(b as char).and_then(|c| node.children[c].as_deref()) else {
+        break;
+      };
+      node = child;
+      if node.is_end {
+        lengths.push(i + 1)
+      }
+    }
+    lengths
+  }
+
+  /// Every way of splitting a run of letters into words from the trie,
+  /// e.g. `"atone"` into `["a", "tone"]` and `["at", "one"]`.
+  ///
+  /// Text with characters outside of `a-z` can't be split at all.
+  pub fn segment(&self, text: &str) -> Vec<Vec<String>> {
+    let bytes = text.as_bytes();
+    // ways[i] holds every split of the text from position i onwards
+    let mut ways: Vec<Vec<Vec<String>>> = vec![vec![]; bytes.len() + 1];
+    ways[bytes.len()].push(vec![]);
+    for start in (0..bytes.len()).rev() {
+      for len in self.prefix_lengths(&bytes[start..]) {
+        let word = &text[start..start + len];
+        let rest = ways[start + len].iter();
+        let splits: Vec<_> = rest
+          .map(|rest| [vec![word.to_owned()], rest.clone()].concat())
+          .collect();
+        ways[start].extend(splits)
+      }
+    }
+    ways.swap_remove(0)
+  }
+}
@@ -1,5 +1,7 @@
 #[cfg(test)]
-use super::{Trie, Word};
+use super::{Arc, Trie, Word};
+#[cfg(test)]
+use crate::trie_of;
 
 #[cfg(test)]
 mod collection_tests {
@@ -65,6 +67,7 @@ mod collection_tests {
       assert!(trie.eq(('a'..='z').map(String::from)));
     }
 
+    #[cfg(feature = "parse")]
     #[test]
     fn ranges() {
       // we should be able to represent character ranges
@@ -72,6 +75,7 @@ mod collection_tests {
       assert!(trie.eq(('f'..='m').map(String::from)));
     }
 
+    #[cfg(feature = "parse")]
     #[test]
     fn char_group() {
       // we should be able to represent character groups
@@ -84,6 +88,7 @@ mod collection_tests {
       assert_eq!(trie.next(), None);
     }
 
+    #[cfg(feature = "parse")]
     #[test]
     fn char_range_group() {
       // we should be able to use multiple ranges in a group
@@ -102,6 +107,7 @@ mod collection_tests {
       assert_eq!(trie.next(), None);
     }
 
+    #[cfg(feature = "parse")]
     #[test]
     fn mixed_group() {
       // we should be able to use a mix of ranges and characters in a group
@@ -123,6 +129,7 @@ mod collection_tests {
       assert_eq!(trie.next(), None);
     }
 
+    #[cfg(feature = "parse")]
     #[test]
     fn two_group() {
       // using two groups should give the cartesian product of both
@@ -134,6 +141,7 @@ mod collection_tests {
       assert_eq!(trie.next(), None);
     }
 
+    #[cfg(feature = "parse")]
     #[test]
     fn mixed_all() {
       // using mixtures of groups, ranges and character should work
@@ -150,7 +158,7 @@ mod collection_tests {
   }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "rand"))]
 mod set_tests {
   use rand::{distributions::Standard, prelude::Distribution, thread_rng, Rng};
 
@@ -169,9 +177,14 @@ mod set_tests {
   fn assert_strs<F: Fn(&str) -> bool>(test: F, no_samples: usize, max_len: usize) {
     assert_all(
       |word: Word| {
-        let mut s = word.to_string();
-        s.truncate(max_len);
-        test(s.as_str())
+        // truncate by whole letters so that groups aren't cut in half
+        let mut len = 0;
+        let letters = word.iter().take_while(|letter| {
+          len += letter.display_len();
+          len <= max_len
+        });
+        let word: Word = letters.copied().collect();
+        test(word.to_string().as_str())
       },
       no_samples,
     )
@@ -258,7 +271,784 @@ mod set_tests {
     #[test]
     fn or_unit() {
       // the empty trie should be the unit of or
-      assert_all(|trie| &trie | Trie::empty() == trie, 1)
+      let trie = Trie::random(&mut thread_rng(), 0.1, 6);
+      assert!(&trie | Trie::empty() == trie)
+    }
+  }
+}
+
+#[cfg(test)]
+mod node_tests {
+  use super::*;
+  use crate::trie::TrieNode;
+
+  #[cfg(feature = "parse")]
+  #[test]
+  fn contains_word() {
+    // only plain words should be accepted
+    let trie = Trie::str("c[ao]t");
+    assert_eq!(trie.contains_word("cat"), Ok(true));
+    assert_eq!(trie.contains_word("ca"), Ok(false));
+    let error = trie.contains_word("c[ao]t").unwrap_err();
+    assert_eq!((error.offset, error.found), (1, Some('[')));
+  }
+
+  #[cfg(feature = "parse")]
+  #[test]
+  fn has_all_groups() {
+    // a group should only need the letters in it, not the whole alphabet
+    let trie = Trie::str("c[ao]t");
+    assert!(trie.has_all("c[ao]t") && trie.has_all("cot"));
+    assert!(!trie.has_all("c[aou]t") && !trie.has_all("c[ao]"));
+  }
+
+  #[test]
+  fn diff_prunes() {
+    // removing every word below a branch should remove the branch too
+    let trie = &(&Trie::str("cat") + "cab") - "cab";
+    assert_eq!(trie, Trie::str("cat"));
+    let mut trie = &Trie::str("cat") + "dog";
+    trie.diff_assign(&Trie::str("dog"));
+    assert!(trie.get_child('d').is_none());
+  }
+
+  #[cfg(feature = "parse")]
+  #[test]
+  fn contains_str() {
+    let trie = Trie::str("c[ao]t");
+    assert!(trie.contains_str("cot"));
+    assert!(!trie.contains_str("co") && !trie.contains_str("cots"));
+    assert!(!trie.contains_str("c[ao]t") && !trie.contains_str("CAT"));
+    assert!(!trie.contains_str("c\u{e1}t"));
+  }
+
+  #[cfg(feature = "parse")]
+  #[test]
+  fn matches_pattern() {
+    let trie = Trie::str("c[ao]t");
+    assert!(trie.matches_pattern(&"[bc]at".parse().unwrap()));
+    assert!(!trie.matches_pattern(&"[bc]ut".parse().unwrap()));
+    assert!(!trie.matches_pattern(&"c.".parse().unwrap()));
+  }
+
+  #[test]
+  fn canonicalize() {
+    // empty branches shouldn't affect equality, and should be removable
+    let mut trie = Trie::str("cat");
+    trie.get_mut_or_insert('d').get_mut_or_insert('o');
+    assert_eq!(trie, Trie::str("cat"));
+    assert_ne!(trie.chars(), ['c']);
+    trie.canonicalize();
+    assert_eq!(trie.chars(), ['c']);
+    assert_ne!(trie, Trie::str("[cd]at"));
+  }
+
+  #[test]
+  fn longest_common_prefix() {
+    // the prefix should stop at a branch or the end of a word
+    assert_eq!(Trie::str("ca[rt]").longest_common_prefix(), "ca");
+    let words = trie_of(&["ca", "cat"]);
+    assert_eq!(words.longest_common_prefix(), "ca");
+    assert_eq!(Trie::empty().longest_common_prefix(), "");
+  }
+
+  #[test]
+  fn longest_prefix_of() {
+    let words = trie_of(&["a", "cat", "cats"]);
+    assert_eq!(words.longest_prefix_of("catsup"), Some("cats"));
+    assert_eq!(words.longest_prefix_of("catalog"), Some("cat"));
+    assert_eq!(words.longest_prefix_of("ca"), None);
+    assert_eq!(words.longest_prefix_of("ant"), Some("a"));
+  }
+
+  #[test]
+  fn is_prefix_free() {
+    assert!(Trie::str("ca[rt]").is_prefix_free());
+    let words = trie_of(&["cat", "cats"]);
+    assert!(!words.is_prefix_free());
+  }
+
+  #[cfg(feature = "parse")]
+  #[test]
+  fn child_mask() {
+    // the child mask should have a bit set for each child
+    let trie = Trie::str("[ace]");
+    assert_eq!(trie.child_mask(), 0b10101);
+  }
+
+  #[cfg(feature = "parse")]
+  #[test]
+  fn mask_round_trip() {
+    // setting children from a mask should give back the same mask
+    let mut trie = Trie::empty();
+    trie.set_children_from_mask(0b1011, &Trie::str(""));
+    assert_eq!(trie.child_mask(), 0b1011);
+    assert_eq!(trie, Trie::str("[abd]"));
+  }
+
+  #[cfg(feature = "parse")]
+  #[test]
+  fn from_word() {
+    // the trait's word constructor should match the boxed trie's
+    let word: Word = "[ab]c[d-f]".parse().unwrap();
+    assert_eq!(
+      <Trie as TrieNode>::from_word(word.clone()),
+      Trie::word(word)
+    );
+  }
+
+  #[test]
+  fn words() {
+    // words should hold one character per letter, in alphabetical order
+    let trie = trie_of(&["be", "a", "ab"]);
+    let words: Vec<Word> = trie.words().collect();
+    let expected = ["a", "ab", "be"].map(|s| s.parse::<Word>().unwrap());
+    assert_eq!(words, expected);
+    assert_eq!(trie.strings().collect::<Vec<_>>(), ["a", "ab", "be"]);
+  }
+
+  #[test]
+  fn retain() {
+    // only matching words should be kept, without any empty branches
+    let mut trie = trie_of(&["a", "an", "and", "bee", "be"]);
+    trie.retain(|word| word.len() < 3);
+    assert_eq!(trie.strings().collect::<Vec<_>>(), ["a", "an", "be"]);
+    assert!(trie
+      .get_child('a')
+      .and_then(|a| a.get_child('n'))
+      .unwrap()
+      .is_leaf());
+  }
+
+  #[test]
+  fn filtered() {
+    // filtering should leave the original trie untouched
+    let trie = trie_of(&["cat", "dog"]);
+    let cats = trie.filtered(|word| word.starts_with('c'));
+    assert_eq!(cats, Trie::str("cat"));
+    assert_eq!(trie.len(), 2);
+    // and share the branches where every word was kept
+    let (c, d) = (&trie.children[2], &cats.children[2]);
+    assert!(Arc::ptr_eq(c.as_ref().unwrap(), d.as_ref().unwrap()));
+    assert!(cats.children[3].is_none());
+  }
+
+  #[cfg(feature = "parse")]
+  #[test]
+  fn walk_with() {
+    // branches in either trie should be visited with their prefixes
+    let (mine, theirs) = (Trie::str("a[bc]"), Trie::str("[ad]"));
+    let mut walk = mine.walk_with(&theirs);
+    let mut steps = vec![];
+    while let Some((c, a, b)) = walk.next() {
+      steps.push((c, walk.prefix().to_owned(), a.is_some(), b.is_some()));
     }
+    let expected = [
+      ("a", true, true),
+      ("ab", true, false),
+      ("ac", true, false),
+      ("d", false, true),
+    ];
+    let expected = expected.map(|(p, a, b)| (p.chars().last().unwrap(), p.to_owned(), a, b));
+    assert_eq!(steps, expected);
+  }
+
+  #[test]
+  fn extract() {
+    // the branches at the end of each word should be found, or the prefix
+    // where this trie falls short
+    let mine = trie_of(&["at", "ate", "bat"]);
+    let theirs = trie_of(&["at", "bat", "cat"]);
+    let found: Vec<_> = mine.extract(&theirs).collect();
+    assert_eq!(found.len(), 3);
+    assert!(found[0].as_ref().is_ok_and(|node| node.has("e")));
+    assert!(found[1].as_ref().is_ok_and(|node| node.is_end));
+    assert_eq!(found[2].as_ref().unwrap_err().prefix, "c");
+  }
+}
+
+#[cfg(test)]
+mod snapshot_tests {
+  use super::*;
+
+  #[test]
+  fn unaffected_by_add() {
+    // adding to the original shouldn't change a snapshot
+    let mut trie = Trie::str("cat");
+    let snapshot = trie.snapshot();
+    trie += "dog";
+    assert!(trie.has("dog"));
+    assert!(!snapshot.has("dog"));
+  }
+
+  #[cfg(feature = "parse")]
+  #[test]
+  fn unaffected_by_sub() {
+    // removing from the original shouldn't change a snapshot
+    let mut trie = Trie::str("ca[rt]");
+    let snapshot = trie.snapshot();
+    trie -= "cat";
+    assert!(!trie.has("cat"));
+    assert!(snapshot.has_all("ca[rt]"));
+  }
+
+  #[test]
+  fn shares_nodes() {
+    // a snapshot shouldn't copy any nodes until either trie is changed
+    let trie = Trie::str("hello");
+    let snapshot = trie.snapshot();
+    assert!(trie
+      .children
+      .iter()
+      .zip(snapshot.children.iter())
+      .all(|(c1, c2)| match (c1, c2) {
+        (Some(n1), Some(n2)) => Arc::ptr_eq(n1, n2),
+        (None, None) => true,
+        _ => false,
+      }));
+  }
+}
+
+#[cfg(test)]
+mod anagram_tests {
+  use super::*;
+  use crate::letter::LetterCounts;
+
+  #[test]
+  fn exact() {
+    // only words using all the letters should be found
+    let trie = trie_of(&["act", "cat", "cats", "at"]);
+    assert_eq!(trie.anagrams("tac"), Some(vec!["act".into(), "cat".into()]));
+  }
+
+  #[test]
+  fn blanks() {
+    // blanks should match any letter, without finding words twice
+    let trie = trie_of(&["cat", "cot", "dog"]);
+    assert_eq!(trie.anagrams("c?t"), Some(vec!["cat".into(), "cot".into()]));
+    assert_eq!(trie.anagrams("ct?"), Some(vec!["cat".into(), "cot".into()]));
+  }
+
+  #[test]
+  fn invalid() {
+    // racks can only contain letters and blanks
+    assert_eq!(Trie::empty().anagrams("ab1"), None);
+  }
+
+  #[cfg(feature = "parse")]
+  #[test]
+  fn options() {
+    // each option should narrow down the words found
+    use crate::trie_ptr::AnagramOptions;
+    let trie = trie_of(&["at", "cat", "cats", "scat", "tacs", "ta"]);
+    let find = |options: AnagramOptions| trie.anagrams_with("stac", &options).unwrap();
+    let partial = AnagramOptions {
+      partial: true,
+      ..Default::default()
+    };
+    assert_eq!(find(partial.clone()).len(), 6);
+    let lengths = AnagramOptions {
+      min_len: 3,
+      max_len: Some(3),
+      ..partial.clone()
+    };
+    assert_eq!(find(lengths), ["cat"]);
+    let must_contain = AnagramOptions {
+      must_contain: LetterCounts::from_text("s"),
+      ..partial.clone()
+    };
+    assert_eq!(find(must_contain), ["cats", "scat", "tacs"]);
+    let pattern = AnagramOptions {
+      pattern: Some("..[st]".parse().unwrap()),
+      ..partial
+    };
+    assert_eq!(find(pattern), ["cat"]);
+  }
+
+  #[test]
+  fn through() {
+    // words should be built around the fixed letters using the rack
+    let trie = trie_of(&["at", "cat", "cats", "scat", "ta", "a"]);
+    let through = |fixed: &[(usize, char)]| trie.anagrams_through("cst", fixed, ..);
+    assert_eq!(
+      through(&[(1, 'a')]),
+      Some(vec!["cat".into(), "cats".into(), "ta".into()])
+    );
+    assert_eq!(through(&[(2, 'a')]), Some(vec!["scat".into()]));
+    assert_eq!(through(&[(0, 'a')]), Some(vec!["at".into()]));
+    let short = trie.anagrams_through("cst", &[(1, 'a')], 2..=3);
+    assert_eq!(short, Some(vec!["cat".into(), "ta".into()]));
+    assert_eq!(trie.anagrams_through("cst", &[(1, '!')], ..), None);
+  }
+
+  #[test]
+  fn from_text() {
+    // words should be found from the letters of arbitrary text
+    let trie = trie_of(&["act", "cat", "cats", "at", "dog"]);
+    let counts = LetterCounts::from_text("T.A.C!");
+    assert_eq!(trie.anagrams_of(&counts), ["act", "cat"]);
+    assert_eq!(trie.words_from(&counts), ["act", "at", "cat"]);
+  }
+}
+
+#[cfg(test)]
+mod batch_tests {
+  use super::*;
+
+  #[test]
+  fn has_many() {
+    // results should be in the same order as the words given
+    let trie = trie_of(&["cat", "cats", "dog"]);
+    let words = ["dog", "ca", "cats", "", "cat", "dogs", "c[ab]t", "cat"];
+    let expected = [true, false, true, false, true, false, false, true];
+    assert_eq!(trie.has_many(words), expected);
+  }
+
+  #[test]
+  #[cfg(feature = "rand")]
+  fn has_many_matches_has() {
+    // batched lookups should agree with single lookups
+    let trie = Trie::random(&mut rand::thread_rng(), 0.1, 6);
+    let words: Vec<String> = trie.clone().chain(["zzz".into(), "a".into()]).collect();
+    let found = trie.has_many(words.iter().map(String::as_str));
+    for (word, found) in words.iter().zip(found) {
+      assert_eq!(trie.has(word), found, "{word}")
+    }
+  }
+
+  #[test]
+  fn anagrams_many() {
+    // racks with the same tiles should get the same anagrams
+    let trie = trie_of(&["act", "cat", "dog"]);
+    let anagrams = trie.anagrams_many(["tac", "god", "cta", "!"]);
+    let cat = Some(vec!["act".to_string(), "cat".to_string()]);
+    assert_eq!(anagrams, [cat.clone(), Some(vec!["dog".into()]), cat, None]);
+  }
+}
+
+#[cfg(test)]
+mod length_tests {
+  use super::*;
+
+  fn words() -> Trie {
+    trie_of(&["a", "at", "ate", "be", "bee", "cat"])
+  }
+
+  #[test]
+  fn words_of_length() {
+    // only words of the given length should be kept
+    let threes = words().words_of_length(3);
+    assert_eq!(threes.collect::<Vec<_>>(), ["ate", "bee", "cat"]);
+    assert!(words().words_of_length(5).is_empty());
+  }
+
+  #[test]
+  fn split_by_length() {
+    // each length should match the words of that length
+    let trie = words();
+    let split = trie.split_by_length();
+    assert_eq!(split.keys().copied().collect::<Vec<_>>(), [1, 2, 3]);
+    for (&len, sub) in &split {
+      assert_eq!(sub, &trie.words_of_length(len), "{len}");
+    }
+  }
+}
+
+#[cfg(test)]
+mod hook_tests {
+  use super::*;
+  use crate::letter::Letter;
+
+  fn words() -> Trie {
+    trie_of(&["at", "ate", "ats", "cat", "hat", "t"])
+  }
+
+  #[test]
+  fn hooks() {
+    // hooks should be the letters that make another word at either end
+    let trie = words();
+    assert_eq!(trie.front_hooks("at"), Letter::from_iter("ch".chars()));
+    assert_eq!(trie.back_hooks("at"), Letter::from_iter("es".chars()));
+    assert_eq!(trie.back_hooks("dog"), Letter::default());
+  }
+
+  #[test]
+  fn annotated_words() {
+    // every word should be annotated, with inner hooks marked
+    let annotated: Vec<_> = words().annotated_words().collect();
+    assert_eq!(annotated.len(), 6);
+    assert_eq!(annotated[0].to_string(), "ch ·at es");
+    assert_eq!(annotated[1].to_string(), " ate· ");
+    assert_eq!(annotated[5].to_string(), "a t ");
+  }
+}
+
+#[cfg(test)]
+mod merge_tests {
+  use super::*;
+  use std::fs;
+
+  #[test]
+  fn load_many() {
+    // merging should union the words and count repeats in later files
+    let paths = ["scrabble_merge_a.txt", "scrabble_merge_b.txt"].map(crate::temp_path);
+    fs::write(&paths[0], "cat\ndog\n").unwrap();
+    fs::write(&paths[1], "dog\nemu\nfox\n").unwrap();
+    let merged = Trie::load_many(&paths);
+    paths.iter().for_each(|path| fs::remove_file(path).unwrap());
+
+    let (trie, report) = merged.unwrap();
+    assert_eq!(trie.len(), 4);
+    assert_eq!(report.total, 4);
+    let counts: Vec<_> = report
+      .sources
+      .iter()
+      .map(|s| (s.words, s.overlap))
+      .collect();
+    assert_eq!(counts, [(2, 0), (3, 1)]);
+    assert_eq!(report.duplicates(), 1);
+  }
+}
+
+#[cfg(test)]
+mod partial_tests {
+  use super::*;
+  use crate::trie::TrieNode;
+  use std::fs;
+
+  #[test]
+  fn load_prefix() {
+    // only the words under the prefix should be loaded
+    let trie = trie_of(&["at", "cab", "cat", "cats", "cot", "dog"]);
+    let path = crate::temp_path("scrabble_load_prefix.tre");
+    trie.save(&path).unwrap();
+
+    let loaded: Vec<_> = ["ca", "", "d", "x", "A"]
+      .map(|prefix| {
+        let loaded = Trie::load_prefix(&path, prefix).unwrap();
+        (loaded, trie.filtered(|word| word.starts_with(prefix)))
+      })
+      .into_iter()
+      .collect();
+    fs::remove_file(&path).unwrap();
+
+    for (loaded, expected) in &loaded[..4] {
+      assert_eq!(loaded, expected);
+    }
+    assert!(loaded[4].0.is_empty());
+  }
+
+  #[test]
+  fn load_prefix_deep() {
+    // a branch too deep to read recursively should still load
+    let depth = 200_000;
+    let mut bytes = 1u32.to_be_bytes().repeat(depth);
+    bytes.extend((1u32 << 26).to_be_bytes());
+    let path = crate::temp_path("scrabble_load_prefix_deep.tre");
+    fs::write(&path, bytes).unwrap();
+    let loaded = Trie::load_prefix(&path, "aa");
+    fs::remove_file(&path).unwrap();
+    assert!(loaded.unwrap().contains_str(&"a".repeat(depth)));
+  }
+}
+
+#[cfg(test)]
+mod metadata_tests {
+  use super::*;
+  use crate::trie_ptr::Metadata;
+  use std::fs;
+
+  #[test]
+  fn round_trip() {
+    // metadata should be readable on its own and not get in the way of loading
+    let trie: Trie = ["cat", "cot"].into_iter().collect();
+    let metadata = Metadata {
+      name: Some("Test".into()),
+      version: Some("2024".into()),
+      source_sha256: Some("ab12".into()),
+      ..Default::default()
+    };
+    let path = crate::temp_path("scrabble_metadata.tre");
+    trie.save_with_metadata(&path, &metadata).unwrap();
+    let read = Trie::metadata(&path).unwrap();
+    let loaded = Trie::file(&path).unwrap();
+    let prefix = Trie::load_prefix(&path, "ca").unwrap();
+    let report = Trie::verify_file(&path, None).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(read, Some(metadata));
+    assert_eq!(loaded, trie);
+    assert!(prefix.has("cat") && !prefix.has("cot"));
+    assert!(report.is_ok(), "{report}");
+  }
+
+  #[test]
+  fn without_metadata() {
+    // plain files should have no metadata
+    let path = crate::temp_path("scrabble_no_metadata.tre");
+    Trie::str("cat").save(&path).unwrap();
+    let read = Trie::metadata(&path);
+    fs::remove_file(&path).unwrap();
+    assert_eq!(read.unwrap(), None);
+  }
+}
+
+#[cfg(test)]
+mod complete_tests {
+  use super::*;
+  use crate::{trie_map::TrieMap, trie_ptr::Ranking};
+
+  fn words() -> Trie {
+    trie_of(&["cat", "cab", "cabs", "cazique", "cot", "ca"])
+  }
+
+  #[test]
+  fn length() {
+    // shorter words should come first, then alphabetically
+    let completions = words().complete("ca", 3, Ranking::Length);
+    assert_eq!(completions, ["ca", "cab", "cat"]);
+    assert!(words().complete("x", 3, Ranking::Length).is_empty());
+  }
+
+  #[test]
+  fn score() {
+    let completions = words().complete("ca", 2, Ranking::Score);
+    assert_eq!(completions, ["cazique", "cabs"]);
+  }
+
+  #[test]
+  fn weights() {
+    // unweighted words should rank below weighted ones
+    let weights: TrieMap<f64> = [("cot".into(), 0.5), ("cat".into(), 2.0)]
+      .into_iter()
+      .collect();
+    let completions = words().complete("c", 3, Ranking::Weights(&weights));
+    assert_eq!(completions, ["cat", "cot", "ca"]);
+  }
+}
+
+#[cfg(test)]
+mod segment_tests {
+  use super::*;
+
+  fn words() -> Trie {
+    trie_of(&["a", "at", "tone", "one", "ton", "e"])
+  }
+
+  #[test]
+  fn segment() {
+    // every split should be found, in alphabetical order of the first word
+    let splits = words().segment("atone");
+    assert_eq!(
+      splits,
+      [vec!["a", "ton", "e"], vec!["a", "tone"], vec!["at", "one"]]
+    );
+  }
+
+  #[test]
+  fn unsplittable() {
+    assert!(words().segment("atones").is_empty());
+    assert!(words().segment("at one").is_empty());
+    assert_eq!(words().segment(""), [Vec::<String>::new()]);
+  }
+}
+
+#[cfg(test)]
+mod limit_tests {
+  use super::*;
+  use crate::limits::{LimitExceeded, Limits};
+
+  #[test]
+  fn pathological() {
+    // huge patterns should fail cleanly rather than exhausting memory
+    let limits = Limits {
+      max_nodes: 10_000,
+      max_results: 10_000,
+      ..Default::default()
+    };
+    let all = Trie::all(30);
+    let result = all.diff_within(&Trie::all(30), &limits);
+    assert_eq!(result, Err(LimitExceeded::Nodes(limits.max_nodes)));
+    assert_eq!(
+      all.strings_within(&limits),
+      Err(LimitExceeded::Results(limits.max_results))
+    );
+    let word = ".".repeat(30).parse().unwrap();
+    assert!(Trie::word_within(word, &limits).is_err());
+  }
+
+  #[test]
+  fn shared() {
+    // combining a trie with itself shouldn't need to walk it
+    let all = Trie::all(30);
+    let limits = Limits {
+      max_nodes: 1,
+      ..Default::default()
+    };
+    assert!(all.and_within(&all.clone(), &limits).is_ok());
+    assert_eq!(all.diff_within(&all.clone(), &limits), Ok(Trie::empty()));
+  }
+
+  #[cfg(feature = "parse")]
+  #[test]
+  fn within_limits() {
+    // small operations should match the unlimited ones
+    let limits = Limits::default();
+    let (a, b) = (Trie::str("c[ao]t"), Trie::str("[bc]at"));
+    assert_eq!(a.and_within(&b, &limits), Ok(&a & b.clone()));
+    assert_eq!(a.or_within(&b, &limits), Ok(&a | b.clone()));
+    assert_eq!(a.diff_within(&b, &limits), Ok(&a / b.clone()));
+    assert_eq!(
+      a.strings_within(&limits),
+      Ok(vec!["cat".into(), "cot".into()])
+    );
+
+    let depth = Limits {
+      max_depth: 2,
+      ..Default::default()
+    };
+    assert_eq!(a.strings_within(&depth), Err(LimitExceeded::Depth(2)));
+  }
+}
+
+#[cfg(test)]
+mod builder_tests {
+  use super::*;
+  use crate::trie_ptr::TrieBuilder;
+
+  fn inserted(words: &[&str]) -> Trie {
+    let mut trie = Trie::empty();
+    for word in words {
+      trie += word
+    }
+    trie
+  }
+
+  #[test]
+  fn sorted() {
+    // sorted words should build the same trie as inserting one at a time
+    let words = ["a", "an", "and", "ant", "ant", "be", "bee", "zoo"];
+    let mut builder = TrieBuilder::new();
+    for word in words {
+      builder.push(word)
+    }
+    assert!(builder.is_sorted());
+    let trie = builder.finish();
+    assert_eq!(trie, inserted(&words));
+    assert_eq!(trie.len(), 7);
+  }
+
+  #[cfg(feature = "parse")]
+  #[test]
+  fn unsorted() {
+    // out of order words and patterns should fall back to insertion
+    let words = ["cat", "bat", "dog"];
+    assert_eq!(TrieBuilder::from_sorted(words), inserted(&words));
+
+    let mut builder = TrieBuilder::new();
+    builder.push("ant");
+    builder.push("c[ao]t");
+    assert!(!builder.is_sorted());
+    assert_eq!(builder.finish(), inserted(&["ant", "cat", "cot"]));
+  }
+}
+
+#[cfg(all(test, feature = "parse"))]
+mod collect_tests {
+  use super::*;
+  use crate::{trie, trie::TrieNode};
+
+  #[test]
+  fn pointer_trie() {
+    // every kind of item should collect to the same trie
+    let strs = ["cat", "cot"];
+    let from_strs: Trie = strs.into_iter().collect();
+    let from_words: Trie = strs.iter().map(|s| s.parse::<Word>().unwrap()).collect();
+    assert_eq!(from_strs, Trie::str("c[ao]t"));
+    assert_eq!(from_words, Trie::str("c[ao]t"));
+
+    let mut trie = from_strs;
+    trie.extend(["dog"]);
+    trie.extend([String::from("cog")]);
+    trie.extend(["[bh]at".parse::<Word>().unwrap()]);
+    assert_eq!(trie.len(), 6);
+  }
+
+  #[test]
+  fn generic_trie() {
+    let text = "cat\ncot\nc[ao]b";
+    let trie: trie::Trie<Trie> = text.lines().collect();
+    let words: Vec<_> = trie.strings().collect();
+    assert_eq!(words, ["cab", "cat", "cob", "cot"]);
+
+    let mut trie = trie;
+    trie.extend([String::from("dog")]);
+    assert!(trie.get_child('d').is_some());
+  }
+}
+
+#[cfg(all(test, feature = "parse"))]
+mod assign_tests {
+  use super::*;
+  use crate::trie;
+
+  fn generic(pattern: &str) -> trie::Trie<Trie> {
+    pattern.parse().unwrap()
+  }
+
+  #[test]
+  fn assign_ops() {
+    // assigning should match the cloning operators
+    let (a, b) = (generic("c[ao][bt]"), generic("[bc]at"));
+    let mut and = generic("c[ao][bt]");
+    and &= &b;
+    assert_eq!(*and, *(&a & &b));
+    let mut or = generic("c[ao][bt]");
+    or |= &b;
+    assert_eq!(*or, *(&a | &b));
+    let mut diff = generic("c[ao][bt]");
+    diff -= &b;
+    assert_eq!(*diff, *(&a - &b));
+    assert_eq!(*diff, &Trie::str("c[ao]b") | Trie::str("cot"));
+  }
+  #[test]
+  fn word_ops() {
+    // the generic wrapper should support the same operators as Trie
+    let trie = generic("c[ao]t");
+    let mut added = generic("c[ao]t");
+    added += "dog";
+    assert_eq!(*added, &Trie::str("c[ao]t") + "dog");
+    assert_eq!(*(&trie + "dog"), *added);
+    let mut removed = generic("c[ao]t");
+    removed -= "cat";
+    assert_eq!(*removed, &Trie::str("c[ao]t") - "cat");
+    assert_eq!(*(&trie - "cat"), *removed);
+    assert_eq!(*(&trie / &generic("cat")), *removed);
+  }
+}
+
+#[cfg(test)]
+mod file_tests {
+  use super::*;
+  use std::fs;
+
+  #[test]
+  fn save_and_load() {
+    // both formats should round trip, and loading should merge
+    let trie = Trie::str("c[ao]t");
+    for ext in ["tre", "txt"] {
+      let path = crate::temp_path(format!("scrabble_save_and_load.{ext}"));
+      trie.save(&path).unwrap();
+      let mut loaded = Trie::str("dog");
+      let result = loaded.load(&path);
+      fs::remove_file(&path).unwrap();
+      result.unwrap();
+      assert_eq!(loaded, &trie + "dog");
+    }
+
+    let path = crate::temp_path("scrabble_save_and_load.csv");
+    let result = trie.save(&path);
+    fs::remove_file(&path).ok();
+    assert!(result.is_err());
   }
 }
@@ -0,0 +1,214 @@
+use super::{metadata::read_metadata, Trie};
+use crate::{
+  alphabet::into_index_unchecked,
+  letter::{bits::MASK, Letter},
+};
+use std::{fmt::Display, fs, io, path::Path, sync::Arc};
+
+/// The bit marking a node as the end of a word in a `.tre` file.
+pub(super) const END: u32 = 1 << 26;
+
+/// A structural problem found in a `.tre` file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+  /// The file ended before all of a node's children were written.
+  Truncated { node: usize },
+  /// There were bytes left over after the last node.
+  TrailingBytes { bytes: usize },
+  /// A node set bits that the format doesn't use.
+  UnknownBits { node: usize, bits: u32 },
+  /// A node with no children that doesn't end a word either.
+  DeadEnd { node: usize },
+}
+
+impl Display for Issue {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Issue::Truncated { node } => write!(f, "the file ends inside node {node}"),
+      Issue::TrailingBytes { bytes } => write!(f, "{bytes} bytes follow the last node"),
+      Issue::UnknownBits { node, bits } => write!(f, "node {node} sets unused bits {bits:#x}"),
+      Issue::DeadEnd { node } => write!(f, "node {node} leads to no words"),
+    }
+  }
+}
+
+/// The result of checking a saved trie.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VerifyReport {
+  /// The number of nodes read.
+  pub nodes: usize,
+  /// The number of words read, up to any point the file was cut off.
+  pub words: usize,
+  pub issues: Vec<Issue>,
+  /// Words in the source list that the file is missing.
+  pub missing: Vec<String>,
+  /// Words in the file that the source list doesn't have.
+  pub extra: Vec<String>,
+}
+
+impl VerifyReport {
+  /// Whether the file is well formed and matches its source, if given.
+  pub fn is_ok(&self) -> bool {
+    self.issues.is_empty() && self.missing.is_empty() && self.extra.is_empty()
+  }
+
+  /// Reads a node's header, noting any issues with it, or `None` if the
+  /// file ends first.
+  fn read_header(&mut self, bytes: &[u8], pos: &mut usize) -> Option<u32> {
+    let Some(chunk) = bytes.get(*pos..*pos + 4) else {
+      self.issues.push(Issue::Truncated { node: self.nodes });
+      return None;
+    };
+    let node = self.nodes;
+    let header = u32::from_be_bytes(chunk.try_into().unwrap());
+    self.nodes += 1;
+    self.words += usize::from(header & END != 0);
+    *pos += 4;
+
+    if header & !(MASK | END) != 0 {
+      let bits = header & !(MASK | END);
+      self.issues.push(Issue::UnknownBits { node, bits });
+    }
+    if header & (MASK | END) == 0 {
+      self.issues.push(Issue::DeadEnd { node });
+    }
+    Some(header)
+  }
+
+  /// Reads a node and its children, noting any issues along the way.
+  ///
+  /// Nodes still waiting on children are kept on a stack rather than
+  /// recursing, so that a corrupt file with a long chain of nodes is
+  /// reported rather than overflowing the stack.
+  fn read(&mut self, bytes: &[u8], pos: &mut usize) -> Option<Trie> {
+    let new_node = |header: u32| {
+      let mut trie = Trie::empty();
+      trie.is_end = header & END != 0;
+      (trie, Letter::from_mask(header))
+    };
+
+    // each frame is a node, the children it's still waiting for
+    // and the character it will be stored under in its parent
+    let (root, pending) = new_node(self.read_header(bytes, pos)?);
+    let mut stack = vec![(root, pending, None)];
+    loop {
+      let (_, pending, _) = stack.last_mut().expect("stack won't be empty");
+      if let Some(c) = pending.next() {
+        let (node, pending) = new_node(self.read_header(bytes, pos)?);
+        stack.push((node, pending, Some(c)));
+        continue;
+      }
+
+      let (node, _, c) = stack.pop().expect("stack won't be empty");
+      match (stack.last_mut(), c) {
+        (Some((parent, _, _)), Some(c)) => {
+          parent.children[into_index_unchecked(c)] = Some(Arc::new(node))
+        }
+        _ => return Some(node),
+      }
+    }
+  }
+}
+
+impl Display for VerifyReport {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{} nodes, {} words", self.nodes, self.words)?;
+    for issue in &self.issues {
+      write!(f, "\n{issue}")?;
+    }
+    if !self.missing.is_empty() {
+      write!(f, "\n{} words missing from the source", self.missing.len())?;
+    }
+    if !self.extra.is_empty() {
+      write!(f, "\n{} words not in the source", self.extra.len())?;
+    }
+    Ok(())
+  }
+}
+
+impl Trie {
+  /// Checks that a `.tre` file is well formed and, given the word list it
+  /// was built from, that it holds exactly the same words.
+  ///
+  /// Only failing to read either file is an error, anything wrong with
+  /// the contents is listed in the report.
+  pub fn verify_file(path: &Path, source: Option<&Path>) -> io::Result<VerifyReport> {
+    let bytes = fs::read(path)?;
+    let mut bytes = bytes.as_slice();
+    read_metadata(&mut bytes)?;
+    let mut report = VerifyReport::default();
+    let mut pos = 0;
+    let trie = report.read(bytes, &mut pos);
+    if trie.is_some() && pos < bytes.len() {
+      report.issues.push(Issue::TrailingBytes {
+        bytes: bytes.len() - pos,
+      });
+    }
+
+    if let Some(source) = source {
+      let trie = trie.unwrap_or_else(Trie::empty);
+      let source = Trie::file(source)?;
+      report.missing = (&source / trie.clone()).collect();
+      report.extra = (&trie / source).collect();
+    }
+    Ok(report)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn verify(bytes: &[u8]) -> VerifyReport {
+    let path = crate::temp_path(format!("scrabble_verify_{}.tre", bytes.len()));
+    fs::write(&path, bytes).unwrap();
+    let report = Trie::verify_file(&path, None);
+    fs::remove_file(&path).unwrap();
+    report.unwrap()
+  }
+
+  #[cfg(feature = "parse")]
+  #[test]
+  fn well_formed() {
+    // a saved trie should verify cleanly
+    let mut bytes = vec![];
+    Trie::str("[bc]a[rt]").save_trie(&mut bytes).unwrap();
+    let report = verify(&bytes);
+    assert!(report.is_ok(), "{report}");
+    assert_eq!(report.words, 4);
+  }
+
+  #[test]
+  fn issues() {
+    // broken files should have each problem reported
+    let truncated = verify(&[0, 0, 0, 0b11]);
+    assert_eq!(truncated.issues, [Issue::Truncated { node: 1 }]);
+
+    let trailing = verify(&[0x04, 0, 0, 0, 0xff]);
+    assert_eq!(trailing.issues, [Issue::TrailingBytes { bytes: 1 }]);
+
+    let unknown = verify(&[0x80, 0, 0, 1, 0, 0, 0, 0]);
+    let expected = [
+      Issue::UnknownBits {
+        node: 0,
+        bits: 1 << 31,
+      },
+      Issue::DeadEnd { node: 1 },
+    ];
+    assert_eq!(unknown.issues, expected);
+  }
+
+  #[test]
+  fn deep_chain() {
+    // long chains should be read through and reported on, not overflow
+    let depth = 200_000;
+    let mut bytes = 1u32.to_be_bytes().repeat(depth);
+    let truncated = verify(&bytes);
+    assert_eq!(truncated.issues, [Issue::Truncated { node: depth }]);
+
+    bytes.extend(END.to_be_bytes());
+    let report = verify(&bytes);
+    assert!(report.is_ok(), "{report}");
+    assert_eq!((report.nodes, report.words), (depth + 1, 1));
+  }
+}
@@ -0,0 +1,95 @@
+/*!
+Helpers for laying out words and patterns in columns.
+
+Every letter is written with ASCII characters, so the display length of a
+letter or word is just the number of bytes it unparses to.
+*/
+
+use super::Word;
+use crate::letter::{unparse::unparse_letter, Letter};
+use std::fmt::{Display, Write};
+
+impl Letter {
+  /// The number of characters used to display this letter.
+  pub fn display_len(&self) -> usize {
+    unparse_letter(self).unwrap().len()
+  }
+}
+
+impl Word {
+  /// The number of characters used to display this word.
+  pub fn display_len(&self) -> usize {
+    self.letters().map(Letter::display_len).sum()
+  }
+
+  /// Displays each word with its letters padded to line up with the
+  /// letters in the same position of every other word, i.e.
+  /// `a[b-d]e` and `[xz]ze` become `a   [b-d]e` and `[xz]z    e`.
+  pub fn align(words: &[Word]) -> Vec<String> {
+    let mut widths = vec![];
+    for word in words {
+      for (i, letter) in word.letters().enumerate() {
+        match widths.get_mut(i) {
+          Some(width) => *width = letter.display_len().max(*width),
+          None => widths.push(letter.display_len()),
+        }
+      }
+    }
+
+    let pad = |word: &Word| {
+      let cells = word.letters().zip(&widths);
+      let line: String = cells.map(|(letter, &w)| format!("{letter:w$}")).collect();
+      line.trim_end().to_owned()
+    };
+    words.iter().map(pad).collect()
+  }
+}
+
+/// Lays items out in rows of equally wide columns, fitting as many columns
+/// as possible into the given line width. Items are separated by two spaces.
+pub fn table<T: Display>(items: &[T], line_width: usize) -> String {
+  let cells: Vec<_> = items.iter().map(T::to_string).collect();
+  let cell_width = cells.iter().map(|c| c.chars().count()).max().unwrap_or(0);
+  let columns = ((line_width + 2) / (cell_width + 2)).max(1);
+
+  let mut table = String::new();
+  for row in cells.chunks(columns) {
+    let line = row.iter().fold(String::new(), |mut line, cell| {
+      write!(line, "{cell:cell_width$}  ").unwrap();
+      line
+    });
+    table.push_str(line.trim_end());
+    table.push('\n');
+  }
+  table
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[cfg(feature = "parse")]
+  #[test]
+  fn display_len() {
+    // the length should match the displayed pattern
+    let word: Word = "a[b-d]e.[xz]".parse().unwrap();
+    assert_eq!(word.display_len(), word.to_string().len());
+    assert_eq!(word.display_len(), 12);
+  }
+
+  #[cfg(feature = "parse")]
+  #[test]
+  fn align() {
+    // letters in the same position should start in the same column
+    let words = ["a[b-d]e", "[xz]ze", "q"].map(|w| w.parse::<Word>().unwrap());
+    assert_eq!(Word::align(&words), ["a   [b-d]e", "[xz]z    e", "q"]);
+  }
+
+  #[test]
+  fn columns() {
+    // items should wrap onto new rows once the line is full
+    let words = ["cat", "dog", "horse", "ox", "yak"];
+    assert_eq!(table(&words, 16), "cat    dog\nhorse  ox\nyak\n");
+    assert_eq!(table(&words, 3), "cat\ndog\nhorse\nox\nyak\n");
+  }
+}
@@ -29,15 +29,19 @@ Disallowed:
 
 - Slicing by individual letters
 - Conversion to and from indices (i.e. a-z <--> 0-25)
-- Random generation of words
+- Random generation of words, with the `rand` feature
 
 */
 
+pub use crate::letter::{Case, ParseError, ParseOptions, ParsedLetter};
 use crate::{letter::Letter, set::Set};
-use std::{collections::VecDeque, ops::Deref};
+use std::{collections::VecDeque, mem, ops::Deref};
 
+pub mod display;
 mod parse;
+#[cfg(feature = "rand")]
 mod random;
+pub mod regex;
 mod unparse;
 
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
@@ -48,6 +52,11 @@ impl Word {
     self.0.push_back(letter)
   }
 
+  /// Pushes a letter holding only the given character, assumed in `a-z`.
+  pub fn push_char(&mut self, c: char) {
+    self.push(Letter::singleton(c))
+  }
+
   pub fn pop(&mut self) -> Option<Letter> {
     self.0.pop_front()
   }
@@ -101,6 +110,8 @@ pub struct StringIter {
   ///
   /// i.e. same number of letters as in word
   letters: Vec<(char, Letter)>,
+  /// Whether the letters haven't been returned yet.
+  fresh: bool,
 }
 
 impl StringIter {
@@ -110,11 +121,13 @@ impl StringIter {
       StringIter {
         word: Word::default(),
         letters: vec![],
+        fresh: false,
       }
     } else {
       let mut iter = StringIter {
         word,
         letters: vec![],
+        fresh: true,
       };
       iter.fill_from();
       iter
@@ -136,14 +149,81 @@ impl StringIter {
 impl Iterator for StringIter {
   type Item = String;
   fn next(&mut self) -> Option<Self::Item> {
-    // remove all empty letters from the back
-    while self.letters.last()?.1.is_empty() {
-      self.letters.pop();
+    if !mem::take(&mut self.fresh) {
+      // remove all empty letters from the back
+      while self.letters.last()?.1.is_empty() {
+        self.letters.pop();
+      }
+      let (c, letter) = self.letters.last_mut()?;
+      *c = letter.next().expect("letter won't be empty");
+      self.fill_from();
     }
-    let (c, letter) = self.letters.last_mut()?;
-    *c = letter.next().expect("letter won't be empty");
-    self.fill_from();
 
     Some(self.letters.iter().map(|(c, _)| *c).collect())
   }
 }
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[cfg(feature = "parse")]
+  #[test]
+  fn strings() {
+    // every combination of letters should be produced, starting with the first
+    let word: Word = "[ab]c[de]".parse().unwrap();
+    let strings: Vec<_> = word.into_iter().collect();
+    assert_eq!(strings, ["acd", "ace", "bcd", "bce"]);
+  }
+
+  #[test]
+  fn lenient() {
+    // lenient parsing should keep the letters before any invalid ones
+    assert!(Word::parse_strict("ab!c").is_err());
+    assert_eq!(Word::parse_lenient("ab!c"), "ab".parse().unwrap());
+  }
+
+  #[cfg(feature = "parse")]
+  #[test]
+  fn case_insensitive() {
+    // uppercase letters should be read as lowercase when allowed
+    assert!(Word::parse_strict("Cat").is_err());
+    let options = ParseOptions {
+      case: Case::Insensitive,
+    };
+    let letters = Word::parse_with("C[A-c]t", &options).unwrap();
+    let word: Word = letters.iter().map(|parsed| parsed.letter).collect();
+    assert_eq!(word, "c[a-c]t".parse().unwrap());
+    assert!(letters.iter().all(|parsed| !parsed.blank));
+  }
+
+  #[test]
+  fn gcg_blanks() {
+    // lowercase letters should be designated blanks in gcg mode
+    let options = ParseOptions { case: Case::Gcg };
+    let letters = Word::parse_with("QuIZ", &options).unwrap();
+    let word: Word = letters.iter().map(|parsed| parsed.letter).collect();
+    assert_eq!(word, "quiz".parse().unwrap());
+    let blanks: Vec<_> = letters.iter().map(|parsed| parsed.blank).collect();
+    assert_eq!(blanks, [false, true, false, false]);
+    let u = Letter::parse_with("u", &options).unwrap();
+    assert_eq!(
+      u,
+      ParsedLetter {
+        letter: "u".parse().unwrap(),
+        blank: true
+      }
+    );
+    let err = Word::parse_with("QU!Z", &options).unwrap_err();
+    assert_eq!((err.offset, err.found), (2, Some('!')));
+  }
+
+  #[test]
+  fn push_char() {
+    // pushed characters should become single letter groups
+    let mut word = Word::default();
+    word.push_char('h');
+    word.push_char('i');
+    assert_eq!(word, "hi".parse().unwrap());
+  }
+}
@@ -1,21 +1,51 @@
-use super::Word;
-use crate::letter::parse::parse_letter;
-use nom::{combinator::map, error::Error, multi::many0, Finish, IResult};
+use super::{ParsedLetter, Word};
+use crate::letter::{parse::parse_letter, ParseError, ParseOptions};
 use std::str::FromStr;
 
-pub(crate) fn parse_word(input: &str) -> IResult<&str, Word> {
-  map(many0(parse_letter), |letters| letters.into_iter().collect())(input)
+impl Word {
+  /// Parses a pattern, failing if any of it isn't a valid letter.
+  pub fn parse_strict(s: &str) -> Result<Self, ParseError> {
+    let letters = Word::parse_with(s, &ParseOptions::default())?;
+    Ok(letters.into_iter().map(|parsed| parsed.letter).collect())
+  }
+
+  /// Parses a pattern with the given options, failing if any of it isn't a
+  /// valid letter. Each letter keeps whether it was marked as a designated
+  /// blank, see [`Case::Gcg`](crate::letter::Case::Gcg).
+  pub fn parse_with(s: &str, options: &ParseOptions) -> Result<Vec<ParsedLetter>, ParseError> {
+    let normalised = options.normalise(s);
+    let mut letters = vec![];
+    let mut input = normalised.as_ref();
+    while !input.is_empty() {
+      let start = s.len() - input.len();
+      match parse_letter(input) {
+        Ok((rest, letter)) => {
+          let blank = options.is_blank(&s[start..s.len() - rest.len()]);
+          letters.push(ParsedLetter { letter, blank });
+          input = rest;
+        }
+        Err(input) => return Err(ParseError::new(s, &s[s.len() - input.len()..])),
+      }
+    }
+    Ok(letters)
+  }
+
+  /// Parses as many letters as possible from the start of a pattern,
+  /// ignoring everything after the first invalid letter.
+  pub fn parse_lenient(s: &str) -> Self {
+    let mut word = Word::default();
+    let mut input = s;
+    while let Ok((rest, letter)) = parse_letter(input) {
+      word.push(letter);
+      input = rest;
+    }
+    word
+  }
 }
 
 impl FromStr for Word {
-  type Err = Error<String>;
+  type Err = ParseError;
   fn from_str(s: &str) -> Result<Self, Self::Err> {
-    match parse_word(s).finish() {
-      Ok((_, word)) => Ok(word),
-      Err(Error { input, code }) => Err(Error {
-        input: input.to_string(),
-        code,
-      }),
-    }
+    Word::parse_strict(s)
   }
 }
@@ -0,0 +1,144 @@
+/*!
+Conversions between word patterns and regular expressions.
+
+Only fixed length regular expressions can be converted to patterns, so
+characters, `.` and character classes (including negated ones) are
+supported, along with `^` and `$` anchors at either end, which every
+pattern implicitly has. Repetition, alternation, groups and escapes are
+rejected.
+*/
+
+use super::Word;
+use crate::{letter::Letter, set::Set};
+use std::{error::Error, fmt::Display};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegexError {
+  /// A regex construct that patterns have no equivalent for.
+  Unsupported { offset: usize, found: char },
+  /// A character class that isn't closed.
+  Unclosed { offset: usize },
+}
+
+impl Display for RegexError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      RegexError::Unsupported { offset, found } => write!(
+        f,
+        "`{found}` at {offset} isn't supported, only a-z, `.` and classes like `[a-d]` are"
+      ),
+      RegexError::Unclosed { offset } => write!(f, "the class opened at {offset} isn't closed"),
+    }
+  }
+}
+
+impl Error for RegexError {}
+
+/// Parses the inside of a character class, starting after the `[`.
+fn parse_class(
+  chars: &mut impl Iterator<Item = (usize, char)>,
+  open: usize,
+) -> Result<Letter, RegexError> {
+  let mut chars = chars.peekable();
+  let negated = chars.next_if(|&(_, c)| c == '^').is_some();
+  let mut letter = Letter::default();
+  loop {
+    let (offset, start) = chars.next().ok_or(RegexError::Unclosed { offset: open })?;
+    match start {
+      ']' => break,
+      'a'..='z' => {}
+      found => return Err(RegexError::Unsupported { offset, found }),
+    }
+
+    let mut end = start;
+    if chars.next_if(|&(_, c)| c == '-').is_some() {
+      end = match chars.next() {
+        Some((_, c @ 'a'..='z')) if c >= start => c,
+        Some((offset, found)) => return Err(RegexError::Unsupported { offset, found }),
+        None => return Err(RegexError::Unclosed { offset: open }),
+      }
+    }
+    letter.extend(start..=end);
+  }
+
+  if negated {
+    letter = Letter::all()
+      .into_iter()
+      .filter(|c| !letter.contains(c))
+      .collect();
+  }
+  Ok(letter)
+}
+
+impl Word {
+  /// A regular expression matching exactly the strings of this pattern.
+  pub fn to_regex(&self) -> String {
+    let letters = self.letters().map(|letter| match letter == &Letter::all() {
+      true => "[a-z]".to_owned(),
+      false => letter.to_string(),
+    });
+    format!("^{}$", letters.collect::<String>())
+  }
+
+  /// Converts a fixed length regular expression into a pattern.
+  pub fn from_regex(regex: &str) -> Result<Word, RegexError> {
+    let inner = regex.strip_prefix('^').unwrap_or(regex);
+    let inner = inner.strip_suffix('$').unwrap_or(inner);
+    // offsets are reported from the start of the whole regex
+    let start = regex.len() - regex.strip_prefix('^').unwrap_or(regex).len();
+
+    let mut word = Word::default();
+    let mut chars = inner.char_indices().map(|(i, c)| (i + start, c));
+    while let Some((offset, c)) = chars.next() {
+      word.push(match c {
+        'a'..='z' => Letter::singleton(c),
+        '.' => Letter::all(),
+        '[' => parse_class(&mut chars, offset)?,
+        found => return Err(RegexError::Unsupported { offset, found }),
+      })
+    }
+    Ok(word)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[cfg(feature = "parse")]
+  #[test]
+  fn round_trip() {
+    // patterns should survive a trip through regex
+    for pattern in ["cat", "c.t", "[ab]x[d-gz]", ""] {
+      let word: Word = pattern.parse().unwrap();
+      assert_eq!(Word::from_regex(&word.to_regex()), Ok(word), "{pattern}");
+    }
+    assert_eq!("c.t".parse::<Word>().unwrap().to_regex(), "^c[a-z]t$");
+  }
+
+  #[cfg(feature = "parse")]
+  #[test]
+  fn negated() {
+    // negated classes should hold every other letter
+    let word = Word::from_regex("[^b-y]").unwrap();
+    assert_eq!(word, "[az]".parse().unwrap());
+  }
+
+  #[test]
+  fn unsupported() {
+    // variable length constructs should be pointed out
+    let err = Word::from_regex("^ca*t$").unwrap_err();
+    assert_eq!(
+      err,
+      RegexError::Unsupported {
+        offset: 3,
+        found: '*'
+      }
+    );
+    assert_eq!(
+      Word::from_regex("a[bc"),
+      Err(RegexError::Unclosed { offset: 1 })
+    );
+    assert!(Word::from_regex("(a|b)").is_err());
+  }
+}
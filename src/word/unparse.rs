@@ -11,6 +11,6 @@ fn unparse_word(word: &Word) -> Result<String, Infallible> {
 
 impl Display for Word {
   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-    write!(f, "{}", unparse_word(self).unwrap())
+    f.pad(&unparse_word(self).unwrap())
   }
 }
@@ -0,0 +1,269 @@
+/*!
+A solver for Wordle-style guessing games.
+
+Feedback on each guess narrows down the answer: greens fix a letter in
+place, yellows rule a letter out of one place but require it elsewhere and
+grays limit how many of a letter there are. [`Constraints`] turns this
+into a [`Word`] pattern to intersect with the dictionary, plus the letter
+counts that a pattern can't express.
+*/
+
+use crate::{alphabet::into_index, letter::Letter, set::Set, trie_ptr::Trie, word::Word};
+use std::{collections::HashMap, error::Error, fmt::Display};
+
+/// The feedback for a single letter of a guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mark {
+  /// The letter is in the right place.
+  Green,
+  /// The letter is in the answer, but somewhere else.
+  Yellow,
+  /// The letter isn't in the answer, or not this many times.
+  Gray,
+}
+
+impl Mark {
+  /// Parses feedback written as `g`, `y` and `.` for each letter.
+  pub fn parse(marks: &str) -> Option<Vec<Mark>> {
+    let mark = |c| match c {
+      'g' => Some(Mark::Green),
+      'y' => Some(Mark::Yellow),
+      '.' => Some(Mark::Gray),
+      _ => None,
+    };
+    marks.chars().map(mark).collect()
+  }
+}
+
+/// The feedback a guess would get for an answer.
+///
+/// Repeated letters are only marked as often as they appear in the
+/// answer, with greens taking priority over yellows.
+pub fn feedback(guess: &str, answer: &str) -> Vec<Mark> {
+  let (guess, answer) = (guess.as_bytes(), answer.as_bytes());
+  let mut marks = vec![Mark::Gray; guess.len()];
+  let mut unmatched = HashMap::new();
+  for (i, &c) in answer.iter().enumerate() {
+    if guess.get(i) == Some(&c) {
+      marks[i] = Mark::Green
+    } else {
+      *unmatched.entry(c).or_insert(0) += 1
+    }
+  }
+  for (mark, c) in marks.iter_mut().zip(guess) {
+    let left = unmatched.get_mut(c).filter(|left| **left > 0);
+    if let (Mark::Gray, Some(left)) = (*mark, left) {
+      *left -= 1;
+      *mark = Mark::Yellow
+    }
+  }
+  marks
+}
+
+/// A guess or its feedback didn't have the same length as the answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WrongLength {
+  pub expected: usize,
+  pub found: usize,
+}
+
+impl Display for WrongLength {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(
+      f,
+      "expected {} letters, found {}",
+      self.expected, self.found
+    )
+  }
+}
+
+impl Error for WrongLength {}
+
+/// Everything learnt about the answer from the feedback so far.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Constraints {
+  /// The letters that could still be in each place.
+  places: Vec<Letter>,
+  /// The fewest of each letter that the answer has.
+  min: [usize; 26],
+  /// The most of each letter that the answer could have.
+  max: [usize; 26],
+}
+
+impl Constraints {
+  /// No constraints on an answer of the given length.
+  pub fn new(len: usize) -> Self {
+    Constraints {
+      places: vec![Letter::all(); len],
+      min: [0; 26],
+      max: [len; 26],
+    }
+  }
+
+  /// Adds the feedback for a guess, which should only use `a-z`.
+  ///
+  /// Fails without adding anything if the guess or the marks aren't as
+  /// long as the answer.
+  pub fn add(&mut self, guess: &str, marks: &[Mark]) -> Result<(), WrongLength> {
+    let expected = self.places.len();
+    for found in [guess.chars().count(), marks.len()] {
+      if found != expected {
+        return Err(WrongLength { expected, found });
+      }
+    }
+
+    let mut found = [0; 26];
+    let mut grayed = [false; 26];
+    for (i, (c, &mark)) in guess.chars().zip(marks).enumerate() {
+      let Some(idx) = into_index(c) else { continue };
+      match mark {
+        Mark::Green => self.places[i] = Letter::singleton(c),
+        Mark::Yellow | Mark::Gray => {
+          self.places[i].delete(&c);
+        }
+      }
+      match mark {
+        Mark::Gray => grayed[idx] = true,
+        _ => found[idx] += 1,
+      }
+    }
+
+    for idx in 0..26 {
+      self.min[idx] = self.min[idx].max(found[idx]);
+      if grayed[idx] {
+        self.max[idx] = self.max[idx].min(found[idx])
+      }
+    }
+    Ok(())
+  }
+
+  /// The pattern that the answer must match.
+  pub fn pattern(&self) -> Word {
+    let unused = |place: &Letter| {
+      let mut place = *place;
+      place.retain(|c| into_index(*c).is_some_and(|i| self.max[i] > 0));
+      place
+    };
+    self.places.iter().map(unused).collect()
+  }
+
+  /// Whether a word could be the answer.
+  pub fn allows(&self, word: &str) -> bool {
+    let mut counts = [0; 26];
+    for c in word.chars() {
+      match into_index(c) {
+        Some(i) => counts[i] += 1,
+        None => return false,
+      }
+    }
+    let places = self.places.iter().zip(word.chars());
+    word.len() == self.places.len()
+      && places.into_iter().all(|(place, c)| place.contains(&c))
+      && (0..26).all(|i| (self.min[i]..=self.max[i]).contains(&counts[i]))
+  }
+
+  /// The words in the dictionary that could still be the answer.
+  pub fn candidates(&self, dict: &Trie) -> Vec<String> {
+    let matches = dict & Trie::word(self.pattern());
+    matches.filter(|word| self.allows(word)).collect()
+  }
+}
+
+/// Ranks guesses by the expected information, in bits, that their feedback
+/// would give about which of the candidates is the answer, best first.
+pub fn rank_guesses<'a>(guesses: &'a [String], candidates: &[String]) -> Vec<(&'a str, f64)> {
+  let total = candidates.len() as f64;
+  let mut ranked: Vec<_> = guesses
+    .iter()
+    .map(|guess| {
+      let mut groups = HashMap::new();
+      for answer in candidates {
+        *groups.entry(feedback(guess, answer)).or_insert(0) += 1;
+      }
+      // sum in a fixed order, so that guesses splitting the candidates the
+      // same way tie exactly rather than by hash order
+      let mut sizes: Vec<_> = groups.into_values().collect();
+      sizes.sort_unstable();
+      let bits = sizes.into_iter().map(|n| {
+        let p = n as f64 / total;
+        -p * p.log2()
+      });
+      (guess.as_str(), bits.sum::<f64>())
+    })
+    .collect();
+  ranked.sort_by(|(g0, b0), (g1, b1)| b1.total_cmp(b0).then_with(|| g0.cmp(g1)));
+  ranked
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn words() -> Vec<String> {
+    [
+      "crane", "crate", "trace", "react", "cater", "eerie", "geese",
+    ]
+    .map(String::from)
+    .to_vec()
+  }
+
+  #[test]
+  fn feedback_repeats() {
+    // repeated letters should only be marked as often as they're found
+    let marks = feedback("geese", "eerie");
+    assert_eq!(marks, Mark::parse(".gy.g").unwrap());
+  }
+
+  #[test]
+  fn candidates() {
+    // candidates should give the same feedback as the answer did
+    let dict: Trie = words().into_iter().collect();
+    let mut constraints = Constraints::new(5);
+    constraints
+      .add("cater", &feedback("cater", "react"))
+      .unwrap();
+    assert_eq!(constraints.candidates(&dict), ["react", "trace"]);
+    constraints
+      .add("trace", &feedback("trace", "react"))
+      .unwrap();
+    assert_eq!(constraints.candidates(&dict), ["react"]);
+  }
+
+  #[test]
+  fn ranking() {
+    // guesses that split the candidates up more should rank higher
+    let candidates = words();
+    let ranked = rank_guesses(&candidates, &candidates);
+    let guesses: Vec<_> = ranked.iter().map(|&(guess, _)| guess).collect();
+    let expected = [
+      "crane", "crate", "react", "trace", "cater", "eerie", "geese",
+    ];
+    assert_eq!(guesses, expected);
+    // the best guesses tell every candidate apart
+    assert!((ranked[0].1 - 7f64.log2()).abs() < 1e-9);
+    assert!(ranked[4].1 < ranked[3].1);
+  }
+
+  #[test]
+  fn wrong_length() {
+    // guesses and marks of the wrong length shouldn't change anything
+    let mut constraints = Constraints::new(3);
+    let err = constraints.add("crane", &[Mark::Gray; 5]);
+    assert_eq!(
+      err,
+      Err(WrongLength {
+        expected: 3,
+        found: 5
+      })
+    );
+    let err = constraints.add("cat", &[Mark::Gray; 2]);
+    assert_eq!(
+      err,
+      Err(WrongLength {
+        expected: 3,
+        found: 2
+      })
+    );
+    assert_eq!(constraints, Constraints::new(3));
+  }
+}
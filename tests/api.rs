@@ -0,0 +1,16 @@
+/*!
+Checks that the types returned from public methods can be named from
+outside the crate, so callers can store them in their own structs.
+*/
+use scrabble::{
+  trie::{DepthFirstIterator, TrieNode},
+  trie_ptr::Trie,
+};
+
+#[test]
+fn names_depth_first_iterator() {
+  let trie = Trie::str("ab");
+  let dfs: DepthFirstIterator<'_, Trie> = trie.dfs();
+  let letters: Vec<_> = dfs.filter_map(|(_, c)| c).collect();
+  assert_eq!(letters, ['a', 'b']);
+}
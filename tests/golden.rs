@@ -0,0 +1,69 @@
+/*!
+Pins the `.tre` format to files saved by earlier versions, so that any
+change to it has to keep reading them.
+*/
+use scrabble::trie_ptr::{Metadata, Trie};
+use std::{fs, path::Path};
+
+const WORDS: &str = include_str!("fixtures/small.txt");
+const PLAIN: &[u8] = include_bytes!("fixtures/small.tre");
+const WITH_METADATA: &[u8] = include_bytes!("fixtures/small_metadata.tre");
+
+fn words() -> Trie {
+  WORDS.lines().collect()
+}
+
+#[test]
+fn reads_plain() {
+  assert_eq!(Trie::from_bytes(PLAIN).unwrap(), words());
+}
+
+#[test]
+fn reads_metadata() {
+  let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/small_metadata.tre");
+  let metadata = Trie::metadata(&path).unwrap().unwrap();
+  assert_eq!(metadata.name.as_deref(), Some("Small"));
+  assert_eq!(metadata.version.as_deref(), Some("1"));
+  assert_eq!(Trie::from_bytes(WITH_METADATA).unwrap(), words());
+  assert_eq!(Trie::file(&path).unwrap(), words());
+}
+
+#[test]
+fn writes_plain() {
+  assert_eq!(words().to_bytes(), PLAIN);
+}
+
+#[test]
+fn writes_metadata() {
+  let metadata = Metadata {
+    name: Some("Small".into()),
+    version: Some("1".into()),
+    ..Default::default()
+  };
+  let path = std::env::temp_dir().join(format!(
+    "{}_scrabble_golden_metadata.tre",
+    std::process::id()
+  ));
+  words().save_with_metadata(&path, &metadata).unwrap();
+  let written = fs::read(&path);
+  fs::remove_file(&path).unwrap();
+  assert_eq!(written.unwrap(), WITH_METADATA);
+}
+
+#[test]
+fn rejects_trailing_bytes() {
+  let mut bytes = PLAIN.to_vec();
+  bytes.push(0);
+  assert!(Trie::from_bytes(&bytes).is_err());
+  assert!(Trie::from_bytes(&PLAIN[..PLAIN.len() - 1]).is_err());
+}
+
+#[test]
+fn reads_deep_chains() {
+  // words too long to read recursively should still load
+  let depth = 200_000;
+  let mut bytes = 1u32.to_be_bytes().repeat(depth);
+  bytes.extend((1u32 << 26).to_be_bytes());
+  let trie = Trie::from_bytes(&bytes).unwrap();
+  assert!(trie.contains_str(&"a".repeat(depth)));
+}